@@ -0,0 +1,884 @@
+// Pure-Rust chunkers that run inside the plugin without a host round-trip.
+use std::{collections::VecDeque, ops::Range};
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    front_matter,
+    language::Language,
+    tokenizer::{ApproxTokenizer, Tokenizer},
+    types::{Chunk, ChunkOptions, ChunkUnit},
+};
+
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?', '。', '！', '？'];
+const CLOSING_PUNCTUATION: &[char] = &['"', '\'', ')', ']', '”', '’', '»'];
+
+/// Splits the text into sentences.
+pub(crate) fn by_sentence(text: &str) -> Vec<Chunk> {
+    to_chunks(text, split_sentences(text))
+}
+
+/// Splits the text into paragraphs, i.e. blocks of text separated by one or more blank lines.
+pub(crate) fn by_paragraph(text: &str) -> Vec<Chunk> {
+    to_chunks(text, split_paragraphs(text))
+}
+
+/// Returns the byte ranges of the sentences of each paragraph of the text, without surrounding
+/// whitespace.
+///
+/// Unlike [`by_sentence`], sentences never span paragraphs and no chunks are built.
+pub(crate) fn sentence_spans(text: &str) -> Vec<Range<usize>> {
+    split_paragraphs(text)
+        .into_iter()
+        .flat_map(|paragraph| {
+            split_sentences(&text[paragraph.clone()])
+                .into_iter()
+                .map(move |sentence| {
+                    paragraph.start + sentence.start..paragraph.start + sentence.end
+                })
+        })
+        .filter_map(|span| trim_span(text, span))
+        .collect()
+}
+
+fn split_sentences(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        if !SENTENCE_TERMINATORS.contains(&c) {
+            continue;
+        }
+
+        // Keep repeated terminators and closing quotes/brackets with the sentence they end.
+        while let Some(&(_, next)) = chars.peek() {
+            if SENTENCE_TERMINATORS.contains(&next) || CLOSING_PUNCTUATION.contains(&next) {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let end = chars.peek().map(|(i, _)| *i).unwrap_or(text.len());
+        let is_boundary =
+            !c.is_ascii() || chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if is_boundary {
+            spans.push(start..end);
+            start = end;
+        }
+    }
+
+    spans.push(start..text.len());
+    spans
+}
+
+fn split_paragraphs(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                spans.push(start..offset);
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        spans.push(start..text.len());
+    }
+
+    spans
+}
+
+/// Splits the text into chunks of at most `max_size` units, preferring to split on the earliest
+/// separator in `separators` that produces small enough pieces, and repeating up to `overlap` units
+/// of the previous chunk at the start of the next one.
+pub(crate) fn recursive(text: &str, opts: &ChunkOptions) -> Vec<Chunk> {
+    by_measure(
+        text,
+        &opts.separators,
+        opts.max_size as usize,
+        opts.overlap as usize,
+        &|s| measure(s, opts.unit),
+    )
+}
+
+/// Same as [`recursive`], but measures chunks with the given tokenizer so that no chunk is larger
+/// than `max_tokens` according to it.
+pub(crate) fn by_tokens(
+    text: &str,
+    tokenizer: &dyn Tokenizer,
+    max_tokens: usize,
+    overlap: usize,
+    separators: &[String],
+) -> Vec<Chunk> {
+    by_measure(text, separators, max_tokens, overlap, &|s| {
+        tokenizer.count_tokens(s)
+    })
+}
+
+fn by_measure(
+    text: &str,
+    separators: &[String],
+    max_size: usize,
+    overlap: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> Vec<Chunk> {
+    let mut pieces = Vec::new();
+    split_recursive(
+        text,
+        0..text.len(),
+        separators,
+        max_size,
+        measure,
+        &mut pieces,
+    );
+
+    // Sizes are measured on the merged text rather than summed, since joining pieces may not add
+    // up exactly (e.g. for tokens).
+    let size = |first: usize, last: usize| measure(&text[pieces[first].start..pieces[last].end]);
+
+    let mut spans = Vec::new();
+    let mut window: VecDeque<usize> = VecDeque::new();
+
+    for i in 0..pieces.len() {
+        if let (Some(&first), Some(&last)) = (window.front(), window.back())
+            && size(first, i) > max_size
+        {
+            spans.push(pieces[first].start..pieces[last].end);
+
+            // Only keep as much of the previous chunk as the overlap (and the next piece) allows.
+            while let Some(&first) = window.front() {
+                if size(first, last) > overlap || size(first, i) > max_size {
+                    window.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        window.push_back(i);
+    }
+
+    if let (Some(&first), Some(&last)) = (window.front(), window.back()) {
+        spans.push(pieces[first].start..pieces[last].end);
+    }
+
+    to_chunks(text, spans)
+}
+
+/// Splits the text into fixed windows of `max_size` units, each repeating the last `overlap` units
+/// of the previous one.
+///
+/// Separators are ignored, so windows may end in the middle of a sentence (or of a word, when
+/// measuring in characters).
+pub(crate) fn fixed_window(text: &str, opts: &ChunkOptions) -> Vec<Chunk> {
+    let units = unit_spans(text, opts.unit);
+    to_chunks(
+        text,
+        windows(&units, opts.max_size as usize, opts.overlap as usize),
+    )
+}
+
+/// Splits Markdown into chunks along its structure.
+///
+/// Chunks never span more than one section (the content under a heading), and consecutive blocks
+/// of a section are grouped until they reach `max_size`. Code blocks, tables and HTML blocks are
+/// never split, even when they are larger than `max_size`; other oversized blocks are split with
+/// [`recursive`]. Each chunk is prefixed with the path of headings it appears under, e.g.
+/// `Install > Linux`, and its offsets point at the content (without the prefix) in the Markdown.
+/// Front matter is skipped.
+pub(crate) fn by_markdown_section(markdown: &str, opts: &ChunkOptions) -> Vec<Chunk> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_FOOTNOTES);
+
+    let mut chunks = Vec::new();
+    let mut section = Section::default();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut heading: Option<(usize, String)> = None;
+    let mut depth = 0;
+
+    let (_, body) = front_matter::split(markdown);
+    let offset = markdown.len() - body.len();
+
+    for (event, range) in Parser::new_ext(body, options).into_offset_iter() {
+        let range = range.start + offset..range.end + offset;
+        match event {
+            Event::Start(Tag::Heading { level, .. }) if depth == 0 => {
+                section.flush(markdown, &mut chunks);
+                heading = Some((level as usize, String::new()));
+                depth += 1;
+            }
+            Event::End(TagEnd::Heading(_)) if depth == 1 => {
+                if let Some((level, text)) = heading.take() {
+                    headings.retain(|(l, _)| *l < level);
+                    headings.push((level, text.trim().to_string()));
+                    section.heading_path = headings.iter().map(|(_, text)| text.clone()).collect();
+                    section.prefix = section.heading_path.join(" > ");
+                }
+                depth -= 1;
+            }
+            Event::Text(text) | Event::Code(text) if heading.is_some() => {
+                if let Some((_, heading)) = heading.as_mut() {
+                    heading.push_str(&text);
+                }
+            }
+            Event::Start(tag) => {
+                if depth == 0 {
+                    let atomic = matches!(tag, Tag::CodeBlock(_) | Tag::Table(_) | Tag::HtmlBlock);
+                    section.push(markdown, range, atomic, opts, &mut chunks);
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            _ => (),
+        }
+    }
+
+    section.flush(markdown, &mut chunks);
+    chunks
+}
+
+// The blocks of the current section that have not been turned into chunks yet.
+#[derive(Default)]
+struct Section {
+    heading_path: Vec<String>,
+    prefix: String,
+    blocks: Vec<Range<usize>>,
+}
+
+impl Section {
+    // The size left for the content of a chunk once the heading prefix (and the blank line joining
+    // it to the content) is added.
+    fn budget(&self, opts: &ChunkOptions) -> usize {
+        let prefix_size = if self.prefix.is_empty() {
+            0
+        } else {
+            measure(&format!("{}\n\n", self.prefix), opts.unit)
+        };
+
+        (opts.max_size as usize).saturating_sub(prefix_size).max(1)
+    }
+
+    fn push(
+        &mut self,
+        markdown: &str,
+        block: Range<usize>,
+        atomic: bool,
+        opts: &ChunkOptions,
+        chunks: &mut Vec<Chunk>,
+    ) {
+        let budget = self.budget(opts);
+        let size = measure(&markdown[block.clone()], opts.unit);
+
+        if size > budget && !atomic {
+            self.flush(markdown, chunks);
+
+            let opts = ChunkOptions {
+                max_size: budget as u32,
+                overlap: opts.overlap.min(budget as u32 - 1),
+                ..opts.clone()
+            };
+            for piece in recursive(&markdown[block.clone()], &opts) {
+                self.emit(
+                    block.start + piece.start..block.start + piece.end,
+                    markdown,
+                    chunks,
+                );
+            }
+            return;
+        }
+
+        // The chunk is measured as it will be emitted, with the separators between its blocks.
+        if let Some(first) = self.blocks.first()
+            && measure(&markdown[first.start..block.end], opts.unit) > budget
+        {
+            self.flush(markdown, chunks);
+        }
+
+        self.blocks.push(block);
+    }
+
+    fn flush(&mut self, markdown: &str, chunks: &mut Vec<Chunk>) {
+        if let (Some(first), Some(last)) = (self.blocks.first(), self.blocks.last()) {
+            self.emit(first.start..last.end, markdown, chunks);
+        }
+
+        self.blocks.clear();
+    }
+
+    fn emit(&self, span: Range<usize>, markdown: &str, chunks: &mut Vec<Chunk>) {
+        for mut chunk in to_chunks(markdown, vec![span]) {
+            if !self.prefix.is_empty() {
+                chunk.text = format!("{}\n\n{}", self.prefix, chunk.text);
+            }
+            chunk.heading_path = self.heading_path.clone();
+            chunks.push(chunk);
+        }
+    }
+}
+
+/// Finds where each chunk returned by the host starts and ends in the original text.
+///
+/// Chunks are expected in document order, possibly overlapping. Chunks that cannot be found
+/// verbatim (e.g. because the host normalised their whitespace) get an empty range at the end of
+/// the previous chunk.
+pub(crate) fn locate(text: &str, pieces: Vec<String>) -> Vec<Chunk> {
+    let mut cursor = 0;
+    let mut last_end = 0;
+
+    pieces
+        .into_iter()
+        .map(|piece| {
+            let start = text[cursor..]
+                .find(piece.as_str())
+                .map(|i| cursor + i)
+                .or_else(|| text.find(piece.as_str()));
+
+            let (start, end) = match start {
+                Some(start) => {
+                    // The next chunk may overlap this one, but never starts at or before it.
+                    cursor = start + piece.chars().next().map_or(0, char::len_utf8);
+                    (start, start + piece.len())
+                }
+                None => (last_end, last_end),
+            };
+
+            last_end = end;
+            Chunk {
+                text: piece,
+                start,
+                end,
+                heading_path: Vec::new(),
+                language: Language::Simple,
+            }
+        })
+        .collect()
+}
+
+/// Returns the number of units in the text.
+///
+/// Tokens are counted with the built-in [`ApproxTokenizer`].
+pub(crate) fn measure(text: &str, unit: ChunkUnit) -> usize {
+    match unit {
+        ChunkUnit::Characters => text.chars().count(),
+        ChunkUnit::Words => text.split_whitespace().count(),
+        ChunkUnit::Tokens => ApproxTokenizer.count_tokens(text),
+    }
+}
+
+/// Returns the byte ranges of every unit in the text.
+fn unit_spans(text: &str, unit: ChunkUnit) -> Vec<Range<usize>> {
+    match unit {
+        ChunkUnit::Characters => text
+            .char_indices()
+            .map(|(i, c)| i..i + c.len_utf8())
+            .collect(),
+        ChunkUnit::Words => {
+            let mut spans = Vec::new();
+            let mut start: Option<usize> = None;
+
+            for (i, c) in text.char_indices() {
+                match (c.is_whitespace(), start) {
+                    (false, None) => start = Some(i),
+                    (true, Some(word_start)) => {
+                        spans.push(word_start..i);
+                        start = None;
+                    }
+                    _ => (),
+                }
+            }
+
+            if let Some(start) = start {
+                spans.push(start..text.len());
+            }
+
+            spans
+        }
+        ChunkUnit::Tokens => ApproxTokenizer.spans(text),
+    }
+}
+
+fn windows(units: &[Range<usize>], size: usize, overlap: usize) -> Vec<Range<usize>> {
+    let step = size.saturating_sub(overlap).max(1);
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < units.len() {
+        let end = (i + size).min(units.len());
+        spans.push(units[i].start..units[end - 1].end);
+
+        if end == units.len() {
+            break;
+        }
+        i += step;
+    }
+
+    spans
+}
+
+fn split_recursive(
+    text: &str,
+    range: Range<usize>,
+    separators: &[String],
+    max_size: usize,
+    measure: &dyn Fn(&str) -> usize,
+    pieces: &mut Vec<Range<usize>>,
+) {
+    let slice = &text[range.clone()];
+    if measure(slice) <= max_size {
+        pieces.push(range);
+        return;
+    }
+
+    let Some(position) = separators
+        .iter()
+        .position(|separator| !separator.is_empty() && slice.contains(separator.as_str()))
+    else {
+        // No separator left to split on, fall back to the longest prefixes that fit.
+        let mut start = range.start;
+        while start < range.end {
+            let end = longest_fit(text, start..range.end, max_size, measure);
+            pieces.push(start..end);
+            start = end;
+        }
+        return;
+    };
+
+    let separator = separators[position].as_str();
+    let remaining = &separators[position + 1..];
+
+    // Separators stay attached to the end of the piece they follow.
+    let mut start = range.start;
+    for (i, _) in slice.match_indices(separator) {
+        let end = range.start + i + separator.len();
+        split_recursive(text, start..end, remaining, max_size, measure, pieces);
+        start = end;
+    }
+
+    if start < range.end {
+        split_recursive(text, start..range.end, remaining, max_size, measure, pieces);
+    }
+}
+
+// Returns the end of the longest prefix of the range that fits in `max_size`, always including at
+// least one character so that progress is made.
+//
+// The prefix is searched in a window that doubles until it no longer fits, so splitting a long run
+// of text without separators stays linear in its length whatever the size of the units.
+fn longest_fit(
+    text: &str,
+    range: Range<usize>,
+    max_size: usize,
+    measure: &dyn Fn(&str) -> usize,
+) -> usize {
+    let first = text[range.clone()]
+        .chars()
+        .next()
+        .map_or(range.start, |c| range.start + c.len_utf8());
+
+    // `fits` is the end of the longest window known to fit, `end` the end of one that doesn't.
+    let mut fits = first;
+    let mut window = max_size.max(1);
+    let end = loop {
+        let mut end = range.start.saturating_add(window).min(range.end).max(first);
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        if measure(&text[range.start..end]) > max_size {
+            break end;
+        }
+        if end == range.end {
+            return end;
+        }
+        fits = end;
+        window = window.saturating_mul(2);
+    };
+
+    let boundaries = text[fits..end]
+        .char_indices()
+        .skip(1)
+        .map(|(i, _)| fits + i)
+        .collect::<Vec<_>>();
+    let fitting =
+        boundaries.partition_point(|&boundary| measure(&text[range.start..boundary]) <= max_size);
+
+    fitting.checked_sub(1).map_or(fits, |i| boundaries[i])
+}
+
+// Converts byte ranges into chunks, trimming surrounding whitespace and dropping empty chunks.
+fn to_chunks(text: &str, spans: Vec<Range<usize>>) -> Vec<Chunk> {
+    spans
+        .into_iter()
+        .filter_map(|span| trim_span(text, span))
+        .map(|span| Chunk {
+            text: text[span.clone()].to_string(),
+            start: span.start,
+            end: span.end,
+            heading_path: Vec::new(),
+            // Languages are detected once all chunks are known, see `transform::chunk`.
+            language: Language::Simple,
+        })
+        .collect()
+}
+
+// Shrinks the range to exclude surrounding whitespace, or returns `None` if it is only whitespace.
+fn trim_span(text: &str, span: Range<usize>) -> Option<Range<usize>> {
+    let slice = &text[span.clone()];
+    let trimmed_start = slice.trim_start();
+    let trimmed = trimmed_start.trim_end();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let start = span.start + (slice.len() - trimmed_start.len());
+    Some(start..start + trimmed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(chunks: &[Chunk]) -> Vec<&str> {
+        chunks.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+
+    fn assert_offsets(text: &str, chunks: &[Chunk]) {
+        for chunk in chunks {
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+        }
+    }
+
+    #[test]
+    fn sentences() {
+        let text = "Hello there! How are you? I'm fine (really.) Version 1.2 is out... Yes.";
+        let chunks = by_sentence(text);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "Hello there!",
+                "How are you?",
+                "I'm fine (really.)",
+                "Version 1.2 is out...",
+                "Yes."
+            ]
+        );
+        assert_offsets(text, &chunks);
+    }
+
+    #[test]
+    fn sentences_cjk() {
+        let text = "你好。今天天气很好！";
+        assert_eq!(texts(&by_sentence(text)), vec!["你好。", "今天天气很好！"]);
+    }
+
+    #[test]
+    fn paragraphs() {
+        let text = "First line\nstill first.\n\n  \nSecond paragraph.\n\n\nThird.";
+        let chunks = by_paragraph(text);
+        assert_eq!(
+            texts(&chunks),
+            vec!["First line\nstill first.", "Second paragraph.", "Third."]
+        );
+        assert_offsets(text, &chunks);
+    }
+
+    #[test]
+    fn sentence_spans_within_paragraphs() {
+        let text = "A heading\n\nFirst sentence. Second one.\n";
+        let spans = sentence_spans(text);
+        let sentences: Vec<&str> = spans.iter().map(|span| &text[span.clone()]).collect();
+        assert_eq!(
+            sentences,
+            vec!["A heading", "First sentence.", "Second one."]
+        );
+    }
+
+    #[test]
+    fn fixed_window_words() {
+        let text = "one two three four five six seven";
+        let opts = ChunkOptions {
+            max_size: 3,
+            overlap: 1,
+            unit: ChunkUnit::Words,
+            ..Default::default()
+        };
+
+        let chunks = fixed_window(text, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec!["one two three", "three four five", "five six seven"]
+        );
+        assert_offsets(text, &chunks);
+    }
+
+    #[test]
+    fn fixed_window_characters() {
+        let opts = ChunkOptions {
+            max_size: 4,
+            overlap: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            texts(&fixed_window("abcdefgh", &opts)),
+            vec!["abcd", "cdef", "efgh"]
+        );
+    }
+
+    #[test]
+    fn recursive_prefers_earlier_separators() {
+        let text = "Alpha beta gamma.\n\nDelta epsilon.\n\nZeta eta theta iota kappa lambda.";
+        let opts = ChunkOptions {
+            max_size: 35,
+            overlap: 0,
+            ..Default::default()
+        };
+
+        let chunks = recursive(text, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "Alpha beta gamma.\n\nDelta epsilon.",
+                "Zeta eta theta iota kappa lambda."
+            ]
+        );
+        assert_offsets(text, &chunks);
+    }
+
+    #[test]
+    fn recursive_with_overlap() {
+        let text = "one two three four five six";
+        let opts = ChunkOptions {
+            max_size: 3,
+            overlap: 1,
+            unit: ChunkUnit::Words,
+            ..Default::default()
+        };
+
+        let chunks = recursive(text, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec!["one two three", "three four five", "five six"]
+        );
+        assert_offsets(text, &chunks);
+        assert!(
+            chunks
+                .iter()
+                .all(|chunk| measure(&chunk.text, ChunkUnit::Words) <= 3)
+        );
+    }
+
+    #[test]
+    fn recursive_hard_splits_long_words() {
+        let opts = ChunkOptions {
+            max_size: 4,
+            overlap: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            texts(&recursive("abcdefghij", &opts)),
+            vec!["abcd", "efgh", "ij"]
+        );
+
+        // Long runs without separators are split in full-size pieces, multi-byte characters too.
+        let text = "数据".repeat(10_000);
+        let chunks = recursive(&text, &opts);
+        assert_eq!(chunks.len(), 5_000);
+        assert!(chunks.iter().all(|chunk| chunk.text == "数据数据"));
+    }
+
+    #[test]
+    fn long_multi_byte_runs_fill_budget() {
+        // Cyrillic and accented Latin tokens are longer than 4 bytes.
+        let tokenizer = ApproxTokenizer;
+        for text in ["приветствиемир".repeat(500), "éèàçüöéèàçüöéè".repeat(500)]
+        {
+            let chunks = by_tokens(&text, &tokenizer, 10, 0, &[]);
+            assert_eq!(
+                chunks
+                    .iter()
+                    .map(|chunk| chunk.text.as_str())
+                    .collect::<String>(),
+                text
+            );
+            assert_offsets(&text, &chunks);
+            for chunk in &chunks[..chunks.len() - 1] {
+                assert!(tokenizer.count_tokens(&chunk.text) <= 10);
+                let next = text[chunk.end..].chars().next().unwrap();
+                let longer = &text[chunk.start..chunk.end + next.len_utf8()];
+                assert!(tokenizer.count_tokens(longer) > 10);
+            }
+        }
+    }
+
+    #[test]
+    fn custom_tokenizer_with_long_tokens() {
+        // One token per 8 bytes, so a budget of 4 tokens covers 32 bytes.
+        let tokenizer = |s: &str| s.len().div_ceil(8);
+        let text = "x".repeat(1_000);
+
+        let chunks = by_tokens(&text, &tokenizer, 4, 0, &[]);
+        assert_eq!(chunks.len(), 32);
+        assert!(chunks[..31].iter().all(|chunk| chunk.text.len() == 32));
+    }
+
+    #[test]
+    fn tokens_fit_budget() {
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        let tokenizer = |s: &str| s.split_whitespace().count() + s.matches('.').count();
+        let separators = ChunkOptions::default().separators;
+
+        let chunks = by_tokens(&text, &tokenizer, 12, 2, &separators);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| tokenizer(&chunk.text) <= 12));
+        assert_offsets(&text, &chunks);
+
+        let chunks = by_tokens(&text, &ApproxTokenizer, 16, 0, &separators);
+        assert!(
+            chunks
+                .iter()
+                .all(|chunk| ApproxTokenizer.count_tokens(&chunk.text) <= 16)
+        );
+    }
+
+    #[test]
+    fn markdown_sections() {
+        let markdown = r#"Intro paragraph.
+
+# Install
+
+Some text.
+
+## Linux
+
+```sh
+apt install hubble
+```
+
+| a | b |
+|---|---|
+| 1 | 2 |
+
+# Usage
+
+Run it.
+"#;
+        let opts = ChunkOptions {
+            max_size: 1000,
+            ..Default::default()
+        };
+
+        let chunks = by_markdown_section(markdown, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "Intro paragraph.",
+                "Install\n\nSome text.",
+                "Install > Linux\n\n```sh\napt install hubble\n```\n\n| a | b |\n|---|---|\n| 1 | 2 |",
+                "Usage\n\nRun it."
+            ]
+        );
+        assert_eq!(&markdown[chunks[2].start..chunks[2].start + 5], "```sh");
+        assert!(chunks[0].heading_path.is_empty());
+        assert_eq!(chunks[2].heading_path, vec!["Install", "Linux"]);
+        assert_eq!(chunks[3].heading_path, vec!["Usage"]);
+    }
+
+    #[test]
+    fn markdown_skips_front_matter() {
+        let markdown = "---\ntitle: Notes\n---\n\n# Notes\n\nBody text.\n";
+        let opts = ChunkOptions {
+            max_size: 1000,
+            ..Default::default()
+        };
+
+        let chunks = by_markdown_section(markdown, &opts);
+        assert_eq!(texts(&chunks), vec!["Notes\n\nBody text."]);
+        assert_eq!(&markdown[chunks[0].start..chunks[0].end], "Body text.");
+    }
+
+    #[test]
+    fn markdown_never_splits_code_blocks() {
+        let markdown = "# Code\n\nShort intro.\n\n```\nlet a = 1;\nlet b = 2;\nlet c = 3;\n```\n";
+        let opts = ChunkOptions {
+            max_size: 20,
+            overlap: 0,
+            ..Default::default()
+        };
+
+        let chunks = by_markdown_section(markdown, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "Code\n\nShort intro.",
+                "Code\n\n```\nlet a = 1;\nlet b = 2;\nlet c = 3;\n```"
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_splits_long_paragraphs() {
+        let markdown = "# Title\n\nOne two three. Four five six. Seven eight nine.\n";
+        let opts = ChunkOptions {
+            max_size: 3,
+            overlap: 0,
+            unit: ChunkUnit::Words,
+            ..Default::default()
+        };
+
+        let chunks = by_markdown_section(markdown, &opts);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "Title\n\nOne two",
+                "Title\n\nthree. Four",
+                "Title\n\nfive six.",
+                "Title\n\nSeven eight",
+                "Title\n\nnine."
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_chunks_fit_with_prefix() {
+        let markdown = "# Guide\n\n## Setup\n\nFirst paragraph.\n\nSecond one.\n\nThird one here.\n\nFourth.\n";
+        let opts = ChunkOptions {
+            max_size: 40,
+            overlap: 0,
+            unit: ChunkUnit::Characters,
+            ..Default::default()
+        };
+
+        let chunks = by_markdown_section(markdown, &opts);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.text.starts_with("Guide > Setup\n\n"));
+            assert!(measure(&chunk.text, opts.unit) <= opts.max_size as usize);
+        }
+    }
+
+    #[test]
+    fn locate_overlapping_chunks() {
+        let text = "one two three two three four";
+        let chunks = locate(
+            text,
+            vec!["one two three".to_string(), "two three four".to_string()],
+        );
+
+        assert_eq!((chunks[0].start, chunks[0].end), (0, 13));
+        assert_eq!((chunks[1].start, chunks[1].end), (14, 28));
+        assert_offsets(text, &chunks);
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::HashSet;
+
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    error::Error,
+    front_matter,
+    types::{CleanOptions, TRACKING_PARAMS},
+};
+
+// Blocks longer than this are content even if they mention a boilerplate phrase.
+const MAX_BOILERPLATE_LENGTH: usize = 200;
+
+/// Applies the cleanup rules to the Markdown and writes it back out.
+///
+/// Front matter is kept as-is. The rest of the document is normalised by the round-trip through
+/// the parser, e.g. runs of blank lines are collapsed and list markers become `*`.
+pub fn clean(markdown: &str, opts: &CleanOptions) -> Result<String, Error> {
+    let (_, body) = front_matter::split(markdown);
+    let mut output = markdown[..markdown.len() - body.len()].to_string();
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+
+    let mut events = clean_inline(Parser::new_ext(body, options), opts);
+    if opts.collapse_whitespace {
+        events = collapse_text(events);
+    }
+    let blocks = filter_blocks(split_blocks(events), opts);
+    let events = blocks.iter().flat_map(|block| block.events.iter());
+
+    // Fences only get longer than the usual three backticks when the code contains a fence.
+    let code_block_token_count =
+        pulldown_cmark_to_cmark::calculate_code_block_token_count(events.clone())
+            .unwrap_or(pulldown_cmark_to_cmark::DEFAULT_CODE_BLOCK_TOKEN_COUNT);
+    pulldown_cmark_to_cmark::cmark_with_options(
+        events,
+        &mut output,
+        pulldown_cmark_to_cmark::Options {
+            code_block_token_count,
+            ..Default::default()
+        },
+    )
+    .map_err(Error::Markdown)?;
+
+    Ok(output)
+}
+
+// Rewrites text, links and images, without changing the blocks of the document.
+fn clean_inline<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+    opts: &CleanOptions,
+) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    // Indexes of the start events of the links being rewritten.
+    let mut links = Vec::new();
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                links.push(events.len());
+                events.push(Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: clean_url(dest_url, opts),
+                    title,
+                    id,
+                }));
+            }
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => events.push(Event::Start(Tag::Image {
+                link_type,
+                dest_url: clean_url(dest_url, opts),
+                title,
+                id,
+            })),
+            Event::End(TagEnd::Link) => {
+                let Some(start) = links.pop() else {
+                    events.push(event);
+                    continue;
+                };
+
+                let has_url = match &events[start] {
+                    Event::Start(Tag::Link { dest_url, .. }) => !dest_url.trim().is_empty(),
+                    _ => true,
+                };
+
+                if !opts.remove_empty_links {
+                    events.push(event);
+                } else if is_blank(&events[start + 1..]) {
+                    events.truncate(start);
+                } else if !has_url {
+                    events.remove(start);
+                } else {
+                    events.push(event);
+                }
+            }
+            _ => events.push(event),
+        }
+    }
+
+    events
+}
+
+// Collapses the whitespace of text outside of code blocks, merging adjacent text events first so
+// that the gaps left by removed links are collapsed too.
+fn collapse_text(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut collapsed: Vec<Event> = Vec::with_capacity(events.len());
+    let mut code_blocks = 0;
+
+    for event in events {
+        let Event::Text(text) = &event else {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => code_blocks += 1,
+                Event::End(TagEnd::CodeBlock) => code_blocks -= 1,
+                _ => (),
+            }
+            collapsed.push(event);
+            continue;
+        };
+        if code_blocks > 0 {
+            collapsed.push(event);
+            continue;
+        }
+
+        match collapsed.last_mut() {
+            Some(Event::Text(previous)) => {
+                *previous = collapse_whitespace(&format!("{}{}", previous, text));
+            }
+            _ => collapsed.push(Event::Text(collapse_whitespace(text))),
+        }
+    }
+
+    collapsed
+}
+
+fn collapse_whitespace(text: &str) -> CowStr<'static> {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+
+    collapsed.into()
+}
+
+fn clean_url<'a>(url: CowStr<'a>, opts: &CleanOptions) -> CowStr<'a> {
+    if !opts.remove_tracking_params {
+        return url;
+    }
+
+    let (without_fragment, fragment) = match url.find('#') {
+        Some(index) => url.split_at(index),
+        None => (url.as_ref(), ""),
+    };
+    let Some((base, query)) = without_fragment.split_once('?') else {
+        return url;
+    };
+
+    let params = query
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            !param.is_empty() && !is_tracking_param(name)
+        })
+        .collect::<Vec<_>>();
+
+    let mut cleaned = base.to_string();
+    if !params.is_empty() {
+        cleaned.push('?');
+        cleaned.push_str(&params.join("&"));
+    }
+    cleaned.push_str(fragment);
+    cleaned.into()
+}
+
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    TRACKING_PARAMS
+        .iter()
+        .any(|param| match param.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == *param,
+        })
+}
+
+// Whether the events render to nothing visible.
+fn is_blank(events: &[Event]) -> bool {
+    events.iter().all(|event| match event {
+        Event::Text(text) | Event::Code(text) => text.trim().is_empty(),
+        Event::SoftBreak | Event::HardBreak => true,
+        Event::Start(tag) => !matches!(tag, Tag::Image { .. }),
+        Event::End(_) => true,
+        _ => false,
+    })
+}
+
+// A top-level block of the document, e.g. a paragraph, a list or a heading.
+struct Block<'a> {
+    events: Vec<Event<'a>>,
+    heading: Option<HeadingLevel>,
+    // The text of the block with whitespace normalised, used to compare blocks.
+    text: String,
+    // Whether the block is a paragraph or list made up only of links (and separators).
+    links_only: bool,
+}
+
+impl<'a> Block<'a> {
+    fn new(events: Vec<Event<'a>>) -> Self {
+        let heading = match events.first() {
+            Some(Event::Start(Tag::Heading { level, .. })) => Some(*level),
+            _ => None,
+        };
+
+        let mut text = String::new();
+        let mut text_outside_links = String::new();
+        let mut has_links = false;
+        let mut link_depth = 0;
+        for event in &events {
+            match event {
+                Event::Start(Tag::Link { .. }) => {
+                    has_links = true;
+                    link_depth += 1;
+                }
+                Event::End(TagEnd::Link) => link_depth -= 1,
+                Event::Text(content) | Event::Code(content) => {
+                    text.push_str(content);
+                    if link_depth == 0 {
+                        text_outside_links.push_str(content);
+                    }
+                }
+                Event::SoftBreak | Event::HardBreak => text.push(' '),
+                _ => (),
+            }
+        }
+
+        let links_only = matches!(
+            events.first(),
+            Some(Event::Start(Tag::Paragraph | Tag::List(_)))
+        ) && has_links
+            && !text_outside_links.chars().any(char::is_alphanumeric);
+
+        Block {
+            heading,
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            links_only,
+            events,
+        }
+    }
+
+    fn is_code(&self) -> bool {
+        matches!(self.events.first(), Some(Event::Start(Tag::CodeBlock(_))))
+    }
+
+    // Blocks that only hold text (and images) and have none left.
+    fn is_empty(&self) -> bool {
+        matches!(
+            self.events.first(),
+            Some(Event::Start(
+                Tag::Paragraph | Tag::Heading { .. } | Tag::List(_) | Tag::BlockQuote(_)
+            ))
+        ) && is_blank(&self.events)
+    }
+
+    fn is_boilerplate(&self, phrases: &[String]) -> bool {
+        let text = self.text.to_lowercase();
+        phrases.iter().any(|phrase| text.contains(phrase.as_str()))
+    }
+}
+
+fn split_blocks(events: Vec<Event<'_>>) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0;
+
+    for event in events {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => (),
+        }
+
+        current.push(event);
+        if depth == 0 {
+            blocks.push(Block::new(std::mem::take(&mut current)));
+        }
+    }
+
+    if !current.is_empty() {
+        blocks.push(Block::new(current));
+    }
+
+    blocks
+}
+
+fn filter_blocks<'a>(blocks: Vec<Block<'a>>, opts: &CleanOptions) -> Vec<Block<'a>> {
+    let first_heading = blocks.iter().position(|block| block.heading.is_some());
+    // The level of the boilerplate section being removed.
+    let mut skipping: Option<HeadingLevel> = None;
+    let mut seen = HashSet::new();
+    let mut kept = Vec::with_capacity(blocks.len());
+
+    for (i, block) in blocks.into_iter().enumerate() {
+        if let Some(level) = skipping {
+            match block.heading {
+                Some(heading) if heading <= level => skipping = None,
+                _ => continue,
+            }
+        }
+
+        if opts.strip_boilerplate {
+            let is_boilerplate = block.is_boilerplate(&opts.boilerplate);
+            if let Some(level) = block.heading
+                && is_boilerplate
+            {
+                skipping = Some(level);
+                continue;
+            }
+
+            let is_short = block.text.len() <= MAX_BOILERPLATE_LENGTH;
+            let is_navigation = block.links_only && first_heading.is_some_and(|first| i < first);
+            if (block.heading.is_none() && is_short && is_boilerplate) || is_navigation {
+                continue;
+            }
+        }
+
+        if opts.collapse_whitespace && block.is_empty() {
+            continue;
+        }
+
+        if opts.dedupe_lines
+            && block.heading.is_none()
+            && !block.is_code()
+            && !block.text.is_empty()
+            && !seen.insert(block.text.clone())
+        {
+            continue;
+        }
+
+        kept.push(block);
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean_default(markdown: &str) -> String {
+        clean(markdown, &CleanOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_strip_boilerplate() {
+        let markdown = "[Home](/) | [Blog](/blog) | [About](/about)\n\n# Article\n\nWe use cookies to improve your experience.\n\nThe actual content.\n\n## Share this\n\n[Twitter](https://twitter.com)\n\n## Conclusion\n\nThe end.";
+        assert_eq!(
+            clean_default(markdown),
+            "# Article\n\nThe actual content.\n\n## Conclusion\n\nThe end."
+        );
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        let markdown =
+            "First   paragraph\twith  spaces.\n\n\n\n\nSecond.\n\n```\nkeep    this\n```";
+        assert_eq!(
+            clean_default(markdown),
+            "First paragraph with spaces.\n\nSecond.\n\n```\nkeep    this\n```"
+        );
+    }
+
+    #[test]
+    fn test_dedupe_lines() {
+        let markdown = "# One\n\nAdvert text\n\nContent.\n\n# One\n\nAdvert  text\n\nMore content.";
+        assert_eq!(
+            clean_default(markdown),
+            "# One\n\nAdvert text\n\nContent.\n\n# One\n\nMore content."
+        );
+    }
+
+    #[test]
+    fn test_remove_tracking_params() {
+        let markdown = "[Link](https://example.com/page?id=1&utm_source=news&fbclid=abc#top) and [other](https://example.com/?utm_medium=email)";
+        assert_eq!(
+            clean_default(markdown),
+            "[Link](https://example.com/page?id=1#top) and [other](https://example.com/)"
+        );
+    }
+
+    #[test]
+    fn test_remove_empty_links() {
+        let markdown = "Before [](https://example.com) after, [text]() and [![Logo](logo.png)](https://example.com).";
+        assert_eq!(
+            clean_default(markdown),
+            "Before after, text and [![Logo](logo.png)](https://example.com)."
+        );
+    }
+
+    #[test]
+    fn test_keeps_front_matter() {
+        let markdown = "---\ntitle: Hello\n---\n# Hello\n\n\n\nWorld";
+        assert_eq!(
+            clean_default(markdown),
+            "---\ntitle: Hello\n---\n# Hello\n\nWorld"
+        );
+    }
+
+    #[test]
+    fn test_rules_can_be_disabled() {
+        let opts = CleanOptions {
+            strip_boilerplate: false,
+            boilerplate: Vec::new(),
+            collapse_whitespace: false,
+            dedupe_lines: false,
+            remove_tracking_params: false,
+            remove_empty_links: false,
+        };
+        let markdown = "We use cookies.\n\nWe use cookies.\n\n[x](https://a.com/?utm_source=b)";
+        assert_eq!(clean(markdown, &opts).unwrap(), markdown);
+    }
+}
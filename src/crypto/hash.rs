@@ -0,0 +1,252 @@
+use std::{collections::HashSet, fmt::Display};
+
+use base64::Engine;
+use sha1::Sha1;
+use sha2::{Digest as _, Sha256, Sha512};
+
+use crate::types::NewChunk;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// SHA-1, only for compatibility with legacy systems
+    Sha1,
+    /// SHA-256 (default)
+    #[default]
+    Sha256,
+    /// SHA-512
+    Sha512,
+    /// BLAKE3 with a 32-byte output
+    Blake3,
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Algorithm::Sha1 => f.write_str("sha1"),
+            Algorithm::Sha256 => f.write_str("sha256"),
+            Algorithm::Sha512 => f.write_str("sha512"),
+            Algorithm::Blake3 => f.write_str("blake3"),
+        }
+    }
+}
+
+/// The output of a hash function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Digest {
+    algorithm: Algorithm,
+    bytes: Vec<u8>,
+}
+
+impl Digest {
+    /// Returns the algorithm used to produce this digest.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Returns the raw bytes of the digest.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the digest and returns the raw bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns the digest as a lowercase hex string.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    /// Returns the digest as a standard (padded) base64 string.
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.bytes)
+    }
+
+    /// Returns the digest as an unpadded URL-safe base64 string.
+    pub fn to_base64_url(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&self.bytes)
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+enum HasherState {
+    Sha1(Sha1),
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+/// An incremental hasher, useful for hashing streamed or chunked data without buffering it.
+pub struct Hasher {
+    algorithm: Algorithm,
+    state: HasherState,
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Self {
+        let state = match algorithm {
+            Algorithm::Sha1 => HasherState::Sha1(Sha1::new()),
+            Algorithm::Sha256 => HasherState::Sha256(Sha256::new()),
+            Algorithm::Sha512 => HasherState::Sha512(Sha512::new()),
+            Algorithm::Blake3 => HasherState::Blake3(Box::new(blake3::Hasher::new())),
+        };
+
+        Hasher { algorithm, state }
+    }
+
+    /// Feeds more data into the hasher.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        match &mut self.state {
+            HasherState::Sha1(hasher) => hasher.update(data),
+            HasherState::Sha256(hasher) => hasher.update(data),
+            HasherState::Sha512(hasher) => hasher.update(data),
+            HasherState::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+
+        self
+    }
+
+    /// Consumes the hasher and returns the digest of all the data fed into it.
+    pub fn finalize(self) -> Digest {
+        let bytes = match self.state {
+            HasherState::Sha1(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha512(hasher) => hasher.finalize().to_vec(),
+            HasherState::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        };
+
+        Digest {
+            algorithm: self.algorithm,
+            bytes,
+        }
+    }
+}
+
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Hashes the given data in one go with the specified algorithm.
+pub fn digest(algorithm: Algorithm, data: &[u8]) -> Digest {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Returns a content address for the given data in the form `<algorithm>:<hex digest>`.
+///
+/// Identical content always produces the same address, so this can be used as a stable key for
+/// deduplication or caching.
+pub fn content_address(algorithm: Algorithm, data: &[u8]) -> String {
+    format!("{}:{}", algorithm, digest(algorithm, data))
+}
+
+/// Removes chunks whose content is identical to an earlier chunk, keeping the first occurrence.
+///
+/// The remaining chunks keep their relative order and are renumbered so that their indexes stay
+/// contiguous, which makes the result safe to pass directly to `entry::create_chunks`.
+pub fn dedupe_chunks(chunks: Vec<NewChunk>) -> Vec<NewChunk> {
+    let mut seen = HashSet::new();
+    let mut deduped = Vec::with_capacity(chunks.len());
+
+    for mut chunk in chunks {
+        if !seen.insert(digest(Algorithm::Blake3, chunk.content.as_bytes())) {
+            continue;
+        }
+
+        chunk.index = deduped.len() as i32;
+        deduped.push(chunk);
+    }
+
+    deduped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = b"Hello, world!";
+
+    #[test]
+    fn test_digest_algorithms() {
+        assert_eq!(
+            digest(Algorithm::Sha1, INPUT).to_hex(),
+            "943a702d06f34599aee1f8da8ef9f7296031d699"
+        );
+        assert_eq!(
+            digest(Algorithm::Sha256, INPUT).to_hex(),
+            "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+        );
+        assert_eq!(digest(Algorithm::Sha512, INPUT).as_bytes().len(), 64);
+        assert_eq!(
+            digest(Algorithm::Blake3, INPUT).to_hex(),
+            "ede5c0b10f2ec4979c69b52f61e42ff5b413519ce09be0f14d098dcfe5f6f98d"
+        );
+    }
+
+    #[test]
+    fn test_incremental_hasher() {
+        let mut hasher = Hasher::new(Algorithm::Sha256);
+        hasher.update(b"Hello, ").update(b"world!");
+
+        assert_eq!(hasher.finalize(), digest(Algorithm::Sha256, INPUT));
+    }
+
+    #[test]
+    fn test_digest_encodings() {
+        let digest = digest(Algorithm::Sha256, INPUT);
+        assert_eq!(
+            digest.to_base64(),
+            "MV9b23bQeMQ7isAGTkoBZGErH853yGk0W/yUx1iU7dM="
+        );
+        assert_eq!(
+            digest.to_base64_url(),
+            "MV9b23bQeMQ7isAGTkoBZGErH853yGk0W_yUx1iU7dM"
+        );
+    }
+
+    #[test]
+    fn test_content_address() {
+        assert_eq!(
+            content_address(Algorithm::Sha256, INPUT),
+            "sha256:315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+        );
+    }
+
+    #[test]
+    fn test_dedupe_chunks() {
+        let chunk = |index: i32, content: &str| NewChunk {
+            entry_id: "entry".to_string(),
+            index,
+            minimum_version: 1,
+            content: content.to_string(),
+            language: "english".to_string(),
+        };
+
+        let chunks = vec![chunk(0, "a"), chunk(1, "b"), chunk(2, "a"), chunk(3, "c")];
+        let deduped = dedupe_chunks(chunks);
+
+        let contents = deduped
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect::<Vec<_>>();
+        let indexes = deduped.iter().map(|c| c.index).collect::<Vec<_>>();
+        assert_eq!(contents, vec!["a", "b", "c"]);
+        assert_eq!(indexes, vec![0, 1, 2]);
+    }
+}
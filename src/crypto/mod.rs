@@ -2,6 +2,19 @@ use std::fmt::Display;
 
 use crate::error::Error;
 
+pub mod hash;
+mod random;
+#[cfg(feature = "rand")]
+mod rng;
+mod seal;
+mod signature;
+
+pub use random::*;
+#[cfg(feature = "rand")]
+pub use rng::*;
+pub use seal::*;
+pub use signature::*;
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum RandSize {
     /// 16 bytes
@@ -56,3 +69,14 @@ pub fn rand(size: RandSize) -> Result<Vec<u8>, Error> {
 
     Ok(buf)
 }
+
+/// Fills the given buffer with random bytes from the host.
+pub fn fill_bytes(dest: &mut [u8]) -> Result<(), Error> {
+    if dest.is_empty() {
+        return Ok(());
+    }
+
+    let buf = rand(RandSize::Custom(dest.len() as u32))?;
+    dest.copy_from_slice(&buf);
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::ops::Range;
+
+use base64::Engine;
+
+use super::{RandSize, rand};
+use crate::error::Error;
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+// The largest multiple of the alphabet size that fits in a byte, bytes above this are rejected to
+// avoid modulo bias.
+const ALPHANUMERIC_LIMIT: u8 = (256 - (256 % ALPHANUMERIC.len())) as u8;
+
+/// Generates a random (version 4) UUID in its hyphenated form.
+pub fn uuid_v4() -> Result<String, Error> {
+    let bytes = rand_array::<16>()?;
+    Ok(format_uuid(build_uuid_v4(bytes)))
+}
+
+/// Generates a time-ordered (version 7) UUID in its hyphenated form.
+///
+/// The timestamp is provided by the host, so UUIDs generated by the same plugin sort by creation
+/// time.
+pub fn uuid_v7() -> Result<String, Error> {
+    let bytes = rand_array::<16>()?;
+    Ok(format_uuid(build_uuid_v7(crate::time::now_millis(), bytes)))
+}
+
+/// Generates a random string of the given length made up of ASCII letters and digits.
+pub fn random_alphanumeric(len: usize) -> Result<String, Error> {
+    let mut output = String::with_capacity(len);
+
+    while output.len() < len {
+        // Request a few more bytes than needed to account for rejected ones.
+        let needed = u32::try_from(len - output.len())
+            .ok()
+            .and_then(|needed| needed.checked_add(needed / 4 + 1))
+            .ok_or_else(|| {
+                Error::InvalidArguments(format!(
+                    "Cannot generate a random string of {} characters",
+                    len
+                ))
+            })?;
+        let bytes = rand(RandSize::Custom(needed))?;
+        output.extend(
+            bytes
+                .into_iter()
+                .filter(|byte| *byte < ALPHANUMERIC_LIMIT)
+                .map(|byte| ALPHANUMERIC[byte as usize % ALPHANUMERIC.len()] as char)
+                .take(len - output.len()),
+        );
+    }
+
+    Ok(output)
+}
+
+/// Generates a URL-safe token from the given number of random bytes.
+///
+/// The token is encoded as unpadded URL-safe base64, so 32 bytes of entropy produce a 43 character
+/// token.
+pub fn random_token(size: RandSize) -> Result<String, Error> {
+    let bytes = rand(size)?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Generates a uniformly distributed random integer in the given (half-open) range.
+///
+/// Values are drawn with rejection sampling, so there is no modulo bias regardless of the size of
+/// the range.
+pub fn random_range(range: Range<u64>) -> Result<u64, Error> {
+    if range.is_empty() {
+        return Err(Error::InvalidArguments(format!(
+            "Cannot generate a random number in an empty range: {:?}",
+            range
+        )));
+    }
+
+    let span = range.end - range.start;
+    // Values at or above `zone` would make some results more likely than others.
+    let zone = u64::MAX - (u64::MAX % span);
+
+    loop {
+        let value = u64::from_le_bytes(rand_array::<8>()?);
+        if value < zone {
+            return Ok(range.start + value % span);
+        }
+    }
+}
+
+fn rand_array<const N: usize>() -> Result<[u8; N], Error> {
+    let bytes = rand(RandSize::Custom(N as u32))?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| Error::BadRandomSize {
+            expected: N as u32,
+            actual: bytes.len() as u32,
+        })
+}
+
+fn build_uuid_v4(mut bytes: [u8; 16]) -> [u8; 16] {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+fn build_uuid_v7(timestamp_ms: u64, mut bytes: [u8; 16]) -> [u8; 16] {
+    // The first 48 bits hold the big-endian UNIX timestamp in milliseconds.
+    bytes[..6].copy_from_slice(&timestamp_ms.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x70;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+fn format_uuid(bytes: [u8; 16]) -> String {
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_uuid_v4() {
+        let uuid = format_uuid(build_uuid_v4([0xff; 16]));
+        assert_eq!(uuid, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+
+        let uuid = format_uuid(build_uuid_v4([0x00; 16]));
+        assert_eq!(uuid, "00000000-0000-4000-8000-000000000000");
+    }
+
+    #[test]
+    fn test_build_uuid_v7() {
+        let uuid = format_uuid(build_uuid_v7(0x0190_1234_5678, [0x00; 16]));
+        assert_eq!(uuid, "01901234-5678-7000-8000-000000000000");
+    }
+
+    #[test]
+    fn test_alphanumeric_limit() {
+        assert_eq!(ALPHANUMERIC.len(), 62);
+        assert_eq!(ALPHANUMERIC_LIMIT, 248);
+    }
+
+    #[test]
+    fn test_random_alphanumeric_too_long() {
+        assert!(matches!(
+            random_alphanumeric(u32::MAX as usize),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+use rand_core::{CryptoRng, RngCore};
+
+use super::RandSize;
+#[cfg(not(test))]
+use super::{fill_bytes, rand};
+#[cfg(test)]
+use tests::{fill_bytes, rand};
+
+// Number of bytes requested from the host at once when serving small reads.
+const BUFFER_SIZE: RandSize = RandSize::Large;
+
+/// A cryptographically secure random number generator backed by the host's entropy source.
+///
+/// Small reads (e.g. `next_u32`) are served from an internal buffer to avoid a host call for
+/// every value, larger reads go straight to the host.
+///
+/// ## Panics
+/// `RngCore` has no way to report errors, so the generator panics if the host fails to provide
+/// random bytes. Use [`super::rand`] or [`super::fill_bytes`] to handle that case gracefully.
+#[derive(Debug, Default)]
+pub struct HostRng {
+    buffer: Vec<u8>,
+}
+
+impl HostRng {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn take(&mut self, dest: &mut [u8]) {
+        let buffer_size: u32 = BUFFER_SIZE.into();
+        if dest.len() >= buffer_size as usize {
+            fill_bytes(dest).expect("Failed to get random bytes from the host");
+            return;
+        }
+
+        if self.buffer.len() < dest.len() {
+            let mut refill = rand(BUFFER_SIZE).expect("Failed to get random bytes from the host");
+            refill.append(&mut self.buffer);
+            self.buffer = refill;
+        }
+
+        let remaining = self.buffer.len() - dest.len();
+        dest.copy_from_slice(&self.buffer[remaining..]);
+        self.buffer.truncate(remaining);
+    }
+}
+
+impl RngCore for HostRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.take(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.take(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.take(dest);
+    }
+}
+
+impl CryptoRng for HostRng {}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, collections::HashSet};
+
+    use super::*;
+    use crate::error::Error;
+
+    thread_local! {
+        static HOST_CALLS: Cell<usize> = const { Cell::new(0) };
+        static NEXT_BYTE: Cell<u8> = const { Cell::new(0) };
+    }
+
+    // Stands in for the host's `crypto_rand`, returning consecutive bytes so that bytes served
+    // twice are easy to spot.
+    pub(super) fn rand(size: RandSize) -> Result<Vec<u8>, Error> {
+        HOST_CALLS.set(HOST_CALLS.get() + 1);
+        let size: u32 = size.into();
+        Ok((0..size)
+            .map(|_| {
+                let byte = NEXT_BYTE.get();
+                NEXT_BYTE.set(byte.wrapping_add(1));
+                byte
+            })
+            .collect())
+    }
+
+    pub(super) fn fill_bytes(dest: &mut [u8]) -> Result<(), Error> {
+        if dest.is_empty() {
+            return Ok(());
+        }
+
+        dest.copy_from_slice(&rand(RandSize::Custom(dest.len() as u32))?);
+        Ok(())
+    }
+
+    fn is_unique(bytes: &[u8]) -> bool {
+        bytes.iter().collect::<HashSet<_>>().len() == bytes.len()
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        for (size, host_calls) in [(0, 0), (1, 1), (7, 1), (63, 1), (64, 1), (200, 1)] {
+            let calls = HOST_CALLS.get();
+            let mut dest = vec![0u8; size];
+            HostRng::new().fill_bytes(&mut dest);
+
+            assert_eq!(HOST_CALLS.get() - calls, host_calls, "size {size}");
+            assert!(is_unique(&dest), "size {size}");
+        }
+    }
+
+    #[test]
+    fn test_fill_bytes_reuses_buffer() {
+        let mut rng = HostRng::new();
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        rng.fill_bytes(&mut first);
+        rng.fill_bytes(&mut second);
+
+        assert_eq!(HOST_CALLS.get(), 1);
+        assert!(is_unique(&[first, second].concat()));
+    }
+
+    #[test]
+    fn test_next_values_use_fresh_bytes() {
+        let mut rng = HostRng::new();
+        let mut bytes = Vec::new();
+        for _ in 0..8 {
+            bytes.extend(rng.next_u32().to_le_bytes());
+        }
+        for _ in 0..4 {
+            bytes.extend(rng.next_u64().to_le_bytes());
+        }
+
+        assert_eq!(HOST_CALLS.get(), 1);
+        assert!(is_unique(&bytes));
+
+        // The buffer is used up, so the next value needs another host call.
+        rng.next_u32();
+        assert_eq!(HOST_CALLS.get(), 2);
+    }
+}
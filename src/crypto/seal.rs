@@ -0,0 +1,216 @@
+use std::fmt::Debug;
+
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+
+use super::{RandSize, constant_time_eq, hmac_sha256, rand};
+use crate::{
+    allocator,
+    error::{CryptoError, Error},
+    host,
+};
+
+/// Size of a secret key in bytes.
+pub const KEY_SIZE: usize = 32;
+
+/// Size of the nonce prepended to every sealed message in bytes.
+pub const NONCE_SIZE: usize = 12;
+
+/// A 256-bit key used to seal and open messages with ChaCha20-Poly1305.
+#[derive(Clone)]
+pub struct SecretKey([u8; KEY_SIZE]);
+
+impl SecretKey {
+    /// Creates a key from raw bytes, the slice must be exactly [`KEY_SIZE`] bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let key: [u8; KEY_SIZE] = bytes.try_into().map_err(|_| {
+            Error::CryptoError(CryptoError::InvalidKeyLength {
+                expected: KEY_SIZE,
+                actual: bytes.len(),
+            })
+        })?;
+
+        Ok(SecretKey(key))
+    }
+
+    /// Generates a new random key with entropy from the host.
+    pub fn generate() -> Result<Self, Error> {
+        Self::from_bytes(&rand(RandSize::Custom(KEY_SIZE as u32))?)
+    }
+
+    /// Derives a key from a plugin-held secret (e.g. a key stored elsewhere) using HKDF-SHA256.
+    ///
+    /// The secret must already be high-entropy: HKDF does no key stretching, so keys derived from
+    /// passwords or other guessable secrets can be brute-forced.
+    ///
+    /// The context separates keys derived from the same secret for different purposes, e.g.
+    /// `"tokens"` and `"webhooks"` produce unrelated keys.
+    pub fn derive(secret: &[u8], context: &str) -> Self {
+        // HKDF-Extract with an empty salt, followed by a single block of HKDF-Expand which is
+        // exactly the size of our key.
+        let prk = hmac_sha256(&[], secret);
+
+        let mut info = Vec::with_capacity(context.len() + 1);
+        info.extend_from_slice(context.as_bytes());
+        info.push(0x01);
+
+        let mut key = [0u8; KEY_SIZE];
+        key.copy_from_slice(&hmac_sha256(&prk, &info));
+        SecretKey(key)
+    }
+
+    /// Requests the plugin's key from the host.
+    ///
+    /// The host derives a stable key for each plugin, so values sealed with it can be opened
+    /// across runs without the plugin having to manage the key itself.
+    pub fn from_host() -> Result<Self, Error> {
+        let encoded = unsafe { host::crypto_plugin_key(0, 0) };
+        let (out_ptr, out_size) = allocator::decode_encoded_ptr("plugin_key", encoded)?;
+        let buf = unsafe { allocator::ptr_to_buffer(out_ptr, out_size) };
+
+        Self::from_bytes(&buf)
+    }
+
+    /// Returns the raw bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; KEY_SIZE] {
+        &self.0
+    }
+}
+
+// Keys are compared in constant time so comparisons don't leak the key material through timing.
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretKey {}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the key material.
+        f.write_str("SecretKey(..)")
+    }
+}
+
+/// Encrypts and authenticates the plaintext with the given key.
+///
+/// A random nonce is drawn from the host for every call and prepended to the ciphertext, so the
+/// output can be passed as-is to [`open`].
+pub fn seal(key: &SecretKey, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    seal_with_aad(key, plaintext, &[])
+}
+
+/// Same as [`seal`], but also authenticates the additional data (which is not encrypted or
+/// included in the output), the same additional data must be provided to open the message.
+pub fn seal_with_aad(key: &SecretKey, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+    let nonce: [u8; NONCE_SIZE] = rand(RandSize::Custom(NONCE_SIZE as u32))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| Error::BadRandomSize {
+            expected: NONCE_SIZE as u32,
+            actual: bytes.len() as u32,
+        })?;
+
+    seal_with_nonce(key, &nonce, plaintext, aad)
+}
+
+/// Decrypts a message produced by [`seal`], failing if it has been tampered with or was sealed
+/// with a different key.
+pub fn open(key: &SecretKey, sealed: &[u8]) -> Result<Vec<u8>, Error> {
+    open_with_aad(key, sealed, &[])
+}
+
+/// Decrypts a message produced by [`seal_with_aad`].
+pub fn open_with_aad(key: &SecretKey, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+    if sealed.len() < NONCE_SIZE {
+        return Err(Error::CryptoError(CryptoError::DecryptionFailed));
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    cipher
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| Error::CryptoError(CryptoError::DecryptionFailed))
+}
+
+fn seal_with_nonce(
+    key: &SecretKey,
+    nonce: &[u8; NONCE_SIZE],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| Error::CryptoError(CryptoError::EncryptionFailed))?;
+
+    let mut sealed = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    sealed.extend_from_slice(nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_key() {
+        // RFC 5869, test case 3
+        let key = SecretKey::derive(&[0x0b; 22], "");
+        assert_eq!(
+            hex::encode(key.as_bytes()),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+        );
+
+        assert_ne!(
+            SecretKey::derive(b"secret", "tokens"),
+            SecretKey::derive(b"secret", "webhooks")
+        );
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = SecretKey::derive(b"secret", "test");
+        let sealed = seal_with_nonce(&key, &[7; NONCE_SIZE], b"refresh-token", b"key").unwrap();
+
+        assert_eq!(&sealed[..NONCE_SIZE], &[7; NONCE_SIZE]);
+        assert_eq!(
+            open_with_aad(&key, &sealed, b"key").unwrap(),
+            b"refresh-token"
+        );
+    }
+
+    #[test]
+    fn test_open_rejects_tampering() {
+        let key = SecretKey::derive(b"secret", "test");
+        let mut sealed = seal_with_nonce(&key, &[7; NONCE_SIZE], b"refresh-token", &[]).unwrap();
+
+        assert!(open(&SecretKey::derive(b"other", "test"), &sealed).is_err());
+        assert!(open_with_aad(&key, &sealed, b"other-key").is_err());
+        assert!(open(&key, &sealed[..4]).is_err());
+
+        sealed[NONCE_SIZE] ^= 1;
+        assert!(open(&key, &sealed).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_length() {
+        assert!(SecretKey::from_bytes(&[0; 16]).is_err());
+        assert!(SecretKey::from_bytes(&[0; KEY_SIZE]).is_ok());
+    }
+}
@@ -0,0 +1,216 @@
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::Sha256;
+
+use crate::error::{CryptoError, Error};
+
+/// The prefix GitHub uses for the `X-Hub-Signature-256` header value.
+pub const GITHUB_SIGNATURE_PREFIX: &str = "sha256=";
+
+/// The default tolerance (in seconds) used when verifying Stripe signatures.
+pub const STRIPE_DEFAULT_TOLERANCE: u64 = 300;
+
+/// Computes the HMAC-SHA256 of the given data with the given key.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length, so this can never fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Computes the HMAC-SHA1 of the given data with the given key.
+///
+/// SHA-1 should only be used to verify signatures from services that still rely on it.
+pub fn hmac_sha1(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Compares two byte slices in constant time (with respect to their contents).
+///
+/// The length of the slices is not considered secret, so slices of different lengths are
+/// rejected immediately.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+/// Verifies a hex-encoded HMAC-SHA256 signature of the given payload.
+pub fn verify_hmac_sha256(secret: &[u8], payload: &[u8], signature_hex: &str) -> Result<(), Error> {
+    let signature = hex::decode(signature_hex.trim())
+        .map_err(|_| Error::CryptoError(CryptoError::MalformedSignature))?;
+
+    if !constant_time_eq(&hmac_sha256(secret, payload), &signature) {
+        return Err(Error::CryptoError(CryptoError::SignatureMismatch));
+    }
+
+    Ok(())
+}
+
+/// Verifies a hex-encoded HMAC-SHA1 signature of the given payload.
+///
+/// SHA-1 should only be used to verify signatures from services that still rely on it.
+pub fn verify_hmac_sha1(secret: &[u8], payload: &[u8], signature_hex: &str) -> Result<(), Error> {
+    let signature = hex::decode(signature_hex.trim())
+        .map_err(|_| Error::CryptoError(CryptoError::MalformedSignature))?;
+
+    if !constant_time_eq(&hmac_sha1(secret, payload), &signature) {
+        return Err(Error::CryptoError(CryptoError::SignatureMismatch));
+    }
+
+    Ok(())
+}
+
+/// Verifies a GitHub webhook payload against the value of its `X-Hub-Signature-256` header.
+///
+/// The header is expected to be in the form `sha256=<hex digest>`.
+pub fn verify_github_signature(secret: &[u8], payload: &[u8], header: &str) -> Result<(), Error> {
+    let signature = header
+        .trim()
+        .strip_prefix(GITHUB_SIGNATURE_PREFIX)
+        .ok_or(Error::CryptoError(CryptoError::MalformedSignature))?;
+
+    verify_hmac_sha256(secret, payload, signature)
+}
+
+/// Verifies a Stripe webhook payload against the value of its `Stripe-Signature` header.
+///
+/// The header is expected to be in the form `t=<timestamp>,v1=<hex digest>[,v1=...]`; any of the
+/// `v1` signatures matching is enough. The timestamp must be within `tolerance` seconds of `now`
+/// (a UNIX timestamp in seconds) to protect against replay attacks.
+pub fn verify_stripe_signature(
+    secret: &[u8],
+    payload: &[u8],
+    header: &str,
+    now: u64,
+    tolerance: u64,
+) -> Result<(), Error> {
+    let mut timestamp: Option<&str> = None;
+    let mut signatures = Vec::new();
+
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = Some(value),
+            Some(("v1", value)) => signatures.push(value),
+            _ => continue,
+        }
+    }
+
+    let timestamp = timestamp.ok_or(Error::CryptoError(CryptoError::MalformedSignature))?;
+    let issued_at = timestamp
+        .parse::<u64>()
+        .map_err(|_| Error::CryptoError(CryptoError::MalformedSignature))?;
+
+    if signatures.is_empty() {
+        return Err(Error::CryptoError(CryptoError::MalformedSignature));
+    }
+
+    if now.abs_diff(issued_at) > tolerance {
+        return Err(Error::CryptoError(CryptoError::TimestampOutOfTolerance {
+            timestamp: issued_at,
+            tolerance,
+        }));
+    }
+
+    let mut signed_payload = Vec::with_capacity(timestamp.len() + 1 + payload.len());
+    signed_payload.extend_from_slice(timestamp.as_bytes());
+    signed_payload.push(b'.');
+    signed_payload.extend_from_slice(payload);
+
+    let expected = hmac_sha256(secret, &signed_payload);
+    let matched = signatures.iter().any(|signature| {
+        hex::decode(signature)
+            .map(|signature| constant_time_eq(&expected, &signature))
+            .unwrap_or(false)
+    });
+
+    if !matched {
+        return Err(Error::CryptoError(CryptoError::SignatureMismatch));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256() {
+        let mac = hmac_sha256(b"key", b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            hex::encode(mac),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha1() {
+        let mac = hmac_sha1(b"key", b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(hex::encode(mac), "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9");
+    }
+
+    #[test]
+    fn test_verify_hmac_sha1() {
+        // Test case 2 of RFC 2202.
+        let signature = "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79";
+        let payload = b"what do ya want for nothing?";
+
+        assert!(verify_hmac_sha1(b"Jefe", payload, signature).is_ok());
+        assert!(matches!(
+            verify_hmac_sha1(b"Jeff", payload, signature),
+            Err(Error::CryptoError(CryptoError::SignatureMismatch))
+        ));
+        assert!(matches!(
+            verify_hmac_sha1(b"Jefe", payload, "not hex"),
+            Err(Error::CryptoError(CryptoError::MalformedSignature))
+        ));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"hello", b"hello"));
+        assert!(!constant_time_eq(b"hello", b"hellp"));
+        assert!(!constant_time_eq(b"hello", b"hell"));
+    }
+
+    #[test]
+    fn test_verify_github_signature() {
+        let payload = b"Hello, World!";
+        let header = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(verify_github_signature(b"It's a Secret to Everybody", payload, header).is_ok());
+        assert!(verify_github_signature(b"wrong secret", payload, header).is_err());
+        assert!(
+            verify_github_signature(b"It's a Secret to Everybody", payload, "deadbeef").is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_stripe_signature() {
+        let secret = b"whsec_test";
+        let payload = br#"{"id":"evt_test"}"#;
+        let signed = hmac_sha256(secret, br#"1700000000.{"id":"evt_test"}"#);
+        let header = format!("t=1700000000,v0=ignored,v1={}", hex::encode(signed));
+
+        assert!(verify_stripe_signature(secret, payload, &header, 1700000100, 300).is_ok());
+        assert!(matches!(
+            verify_stripe_signature(secret, payload, &header, 1700001000, 300),
+            Err(Error::CryptoError(
+                CryptoError::TimestampOutOfTolerance { .. }
+            ))
+        ));
+        assert!(matches!(
+            verify_stripe_signature(b"other", payload, &header, 1700000000, 300),
+            Err(Error::CryptoError(CryptoError::SignatureMismatch))
+        ));
+        assert!(verify_stripe_signature(secret, payload, "v1=abc", 1700000000, 300).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+// Wraps source code in a fenced code block tagged with its language.
+
+// File extensions (or names) and the language they are tagged with.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("bash", "bash"),
+    ("c", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("cs", "csharp"),
+    ("css", "css"),
+    ("dart", "dart"),
+    ("dockerfile", "dockerfile"),
+    ("ex", "elixir"),
+    ("exs", "elixir"),
+    ("go", "go"),
+    ("h", "c"),
+    ("hpp", "cpp"),
+    ("hs", "haskell"),
+    ("html", "html"),
+    ("java", "java"),
+    ("js", "javascript"),
+    ("json", "json"),
+    ("jsx", "jsx"),
+    ("kt", "kotlin"),
+    ("lua", "lua"),
+    ("makefile", "makefile"),
+    ("md", "markdown"),
+    ("mjs", "javascript"),
+    ("php", "php"),
+    ("pl", "perl"),
+    ("proto", "protobuf"),
+    ("py", "python"),
+    ("rb", "ruby"),
+    ("rs", "rust"),
+    ("scala", "scala"),
+    ("sh", "bash"),
+    ("sql", "sql"),
+    ("swift", "swift"),
+    ("toml", "toml"),
+    ("ts", "typescript"),
+    ("tsx", "tsx"),
+    ("xml", "xml"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("zig", "zig"),
+    ("zsh", "bash"),
+];
+
+/// Returns the language of a file from its name, e.g. `rust` for `main.rs`.
+pub(crate) fn language(file_name: &str) -> Option<&'static str> {
+    let name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(file_name)
+        .to_ascii_lowercase();
+    let extension = name.rsplit_once('.').map_or(name.as_str(), |(_, ext)| ext);
+
+    LANGUAGES
+        .iter()
+        .find(|(key, _)| *key == extension)
+        .map(|(_, language)| *language)
+}
+
+/// Renders the source as a fenced code block, tagged with the language of the file.
+pub(crate) fn to_markdown(source: &str, file_name: &str) -> String {
+    let source = source.trim_end_matches(['\n', '\r']);
+
+    // The fence must be longer than any run of backticks in the code.
+    let mut fence = "```".to_string();
+    while source.contains(&fence) {
+        fence.push('`');
+    }
+
+    format!(
+        "{fence}{}\n{source}\n{fence}",
+        language(file_name).unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language() {
+        assert_eq!(language("src/main.rs"), Some("rust"));
+        assert_eq!(language("Dockerfile"), Some("dockerfile"));
+        assert_eq!(language("archive.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_code_to_markdown() {
+        assert_eq!(
+            to_markdown("fn main() {}\n", "main.rs"),
+            "```rust\nfn main() {}\n```"
+        );
+        assert_eq!(
+            to_markdown("Use ```code``` blocks", "README"),
+            "````\nUse ```code``` blocks\n````"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Converts delimited text (CSV, TSV...) into a Markdown table.
+
+const DELIMITERS: &[char] = &[',', ';', '\t', '|'];
+
+/// Renders the delimited text as a Markdown table, with the first row as its header.
+///
+/// The delimiter is whichever of `,`, `;`, tab or `|` is most common in the first line, and quoted
+/// fields (with `""` escapes) may span multiple lines.
+pub(crate) fn to_markdown(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = DELIMITERS
+        .iter()
+        .copied()
+        .max_by_key(|delimiter| first_line.matches(*delimiter).count())
+        .unwrap_or(',');
+
+    super::table(&parse(text, delimiter))
+}
+
+fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            _ if in_quotes => field.push(c),
+            '\r' => (),
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ if c == delimiter => row.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    // Blank lines are not rows.
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_to_markdown() {
+        let csv = "name,notes\r\nAda,\"Wrote the first program, \"\"probably\"\"\"\r\n\r\nGrace,\"Multi\nline\"\n";
+        assert_eq!(
+            to_markdown(csv),
+            "| name | notes |\n| --- | --- |\n| Ada | Wrote the first program, \"probably\" |\n| Grace | Multi line |"
+        );
+    }
+
+    #[test]
+    fn test_detects_delimiter() {
+        assert_eq!(
+            to_markdown("a;b\n1,5;2"),
+            "| a | b |\n| --- | --- |\n| 1,5 | 2 |"
+        );
+        assert_eq!(
+            to_markdown("a\tb\tc\n1\t2"),
+            "| a | b | c |\n| --- | --- | --- |\n| 1 | 2 |  |"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+// Converts Word documents (DOCX) to Markdown from the paragraphs and tables of
+// `word/document.xml`. Headings are recognised by their built-in styles and list items by their
+// numbering, but the numbering definitions themselves are not read so all lists are bulleted.
+
+use std::collections::HashMap;
+
+use super::{relationships, zip::ZipArchive};
+use crate::{
+    error::{DocumentError, Error},
+    html::{dom::Element, escape, escape_line_starts},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Format {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+}
+
+enum Segment {
+    Text(Format, String),
+    Markdown(String),
+}
+
+pub(crate) fn to_markdown(bytes: &[u8]) -> Result<String, Error> {
+    let archive = ZipArchive::new(bytes)?;
+    let document = crate::html::dom::parse(&archive.read_string("word/document.xml")?);
+    let links = relationships(&archive, "word/_rels/document.xml.rels");
+
+    let body = document.find("w:body").ok_or_else(|| {
+        Error::DocumentError(DocumentError::MissingPart {
+            part: "document body".to_string(),
+        })
+    })?;
+
+    let mut blocks = Vec::new();
+    collect_blocks(body, &links, &mut blocks);
+    Ok(super::join_blocks(&blocks))
+}
+
+fn collect_blocks(
+    element: &Element,
+    links: &HashMap<String, String>,
+    blocks: &mut Vec<(String, bool)>,
+) {
+    for child in element.elements() {
+        match child.name.as_str() {
+            "w:p" => blocks.extend(paragraph(child, links)),
+            "w:tbl" => {
+                let table = table(child, links);
+                if !table.is_empty() {
+                    blocks.push((table, false));
+                }
+            }
+            // Content controls and custom XML wrap regular paragraphs.
+            "w:sdt" | "w:sdtcontent" | "w:customxml" => collect_blocks(child, links, blocks),
+            _ => (),
+        }
+    }
+}
+
+// Returns the paragraph as Markdown, and whether it is a list item.
+fn paragraph(element: &Element, links: &HashMap<String, String>) -> Option<(String, bool)> {
+    let mut segments = Vec::new();
+    collect_segments(element, links, &mut segments);
+    let text = render(&segments);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let properties = child(element, "w:ppr");
+    let style = properties
+        .and_then(|properties| child(properties, "w:pstyle"))
+        .and_then(|style| style.attr("w:val"))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if let Some(level) = heading_level(&style) {
+        return Some((
+            format!(
+                "{} {}",
+                "#".repeat(level),
+                escape_line_starts(&text.replace('\n', " "))
+            ),
+            false,
+        ));
+    }
+
+    if let Some(numbering) = properties.and_then(|properties| child(properties, "w:numpr")) {
+        let level = child(numbering, "w:ilvl")
+            .and_then(|level| level.attr("w:val"))
+            .and_then(|level| level.parse::<usize>().ok())
+            .unwrap_or(0);
+        let item = format!(
+            "{}- {}",
+            "  ".repeat(level),
+            escape_line_starts(&text.replace('\n', " "))
+        );
+        return Some((item, true));
+    }
+
+    Some((escape_line_starts(text).replace('\n', "  \n"), false))
+}
+
+fn heading_level(style: &str) -> Option<usize> {
+    match style {
+        "title" => Some(1),
+        "subtitle" => Some(2),
+        _ => style
+            .strip_prefix("heading")
+            .and_then(|level| level.trim().parse().ok())
+            .filter(|level| (1..=6).contains(level)),
+    }
+}
+
+fn collect_segments(
+    element: &Element,
+    links: &HashMap<String, String>,
+    segments: &mut Vec<Segment>,
+) {
+    for child in element.elements() {
+        match child.name.as_str() {
+            "w:r" => segments.push(run(child)),
+            "w:hyperlink" => {
+                let mut inner = Vec::new();
+                collect_segments(child, links, &mut inner);
+                let text = render(&inner);
+
+                let url = child.attr("r:id").and_then(|id| links.get(id));
+                match url {
+                    Some(url) if !text.trim().is_empty() => {
+                        segments.push(Segment::Markdown(format!("[{text}]({url})")))
+                    }
+                    _ => segments.push(Segment::Markdown(text)),
+                }
+            }
+            // Deleted revisions, properties and field codes are not part of the text.
+            "w:del" | "w:ppr" | "w:rpr" | "w:instrtext" => (),
+            // Insertions, smart tags, fields...
+            _ => collect_segments(child, links, segments),
+        }
+    }
+}
+
+fn run(element: &Element) -> Segment {
+    let properties = child(element, "w:rpr");
+    let format = Format {
+        bold: is_on(properties, "w:b"),
+        italic: is_on(properties, "w:i"),
+        strike: is_on(properties, "w:strike"),
+    };
+
+    let mut text = String::new();
+    for child in element.elements() {
+        match child.name.as_str() {
+            "w:t" => text.push_str(&child.text()),
+            "w:tab" => text.push(' '),
+            "w:br" | "w:cr" => text.push('\n'),
+            _ => (),
+        }
+    }
+
+    Segment::Text(format, text)
+}
+
+// Whether a toggle property like `<w:b/>` is set, it can be turned off with `w:val="0"`.
+fn is_on(properties: Option<&Element>, name: &str) -> bool {
+    properties
+        .and_then(|properties| child(properties, name))
+        .is_some_and(|property| !matches!(property.attr("w:val"), Some("0" | "false" | "none")))
+}
+
+// Renders the segments, merging adjacent runs with the same formatting so Word's habit of
+// splitting text into many runs doesn't show up as `**a****b**`.
+fn render(segments: &[Segment]) -> String {
+    let mut output = String::new();
+    let mut pending: Option<(Format, String)> = None;
+
+    for segment in segments {
+        match segment {
+            Segment::Text(format, text) => match &mut pending {
+                Some((current, buffer)) if current == format => buffer.push_str(text),
+                _ => {
+                    if let Some((format, text)) = pending.take() {
+                        output.push_str(&formatted(format, &text));
+                    }
+                    pending = Some((*format, text.clone()));
+                }
+            },
+            Segment::Markdown(markdown) => {
+                if let Some((format, text)) = pending.take() {
+                    output.push_str(&formatted(format, &text));
+                }
+                output.push_str(markdown);
+            }
+        }
+    }
+
+    if let Some((format, text)) = pending {
+        output.push_str(&formatted(format, &text));
+    }
+    output
+}
+
+fn formatted(format: Format, text: &str) -> String {
+    let escaped = escape(text);
+    let trimmed = escaped.trim();
+    if trimmed.is_empty() {
+        return escaped;
+    }
+
+    let mut marker = String::new();
+    if format.strike {
+        marker.push_str("~~");
+    }
+    if format.bold {
+        marker.push_str("**");
+    }
+    if format.italic {
+        marker.push('*');
+    }
+    if marker.is_empty() {
+        return escaped;
+    }
+
+    // Markers must hug the text, so surrounding whitespace is moved outside of them.
+    let closing = marker.chars().rev().collect::<String>();
+    let leading = if escaped.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trailing = if escaped.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{leading}{marker}{trimmed}{closing}{trailing}")
+}
+
+fn table(element: &Element, links: &HashMap<String, String>) -> String {
+    let rows = element
+        .elements()
+        .filter(|row| row.name == "w:tr")
+        .map(|row| {
+            row.elements()
+                .filter(|cell| cell.name == "w:tc")
+                .map(|cell| {
+                    cell.elements()
+                        .filter(|paragraph| paragraph.name == "w:p")
+                        .filter_map(|element| paragraph(element, links))
+                        .map(|(text, _)| text)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    super::table(&rows)
+}
+
+fn child<'a>(element: &'a Element, name: &str) -> Option<&'a Element> {
+    element.elements().find(|child| child.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::zip::build;
+    use super::*;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <w:body>
+    <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Report</w:t></w:r></w:p>
+    <w:p>
+      <w:r><w:t xml:space="preserve">Plain, </w:t></w:r>
+      <w:r><w:rPr><w:b/></w:rPr><w:t>bo</w:t></w:r>
+      <w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">ld </w:t></w:r>
+      <w:r><w:rPr><w:b w:val="0"/></w:rPr><w:t>and a </w:t></w:r>
+      <w:hyperlink r:id="rId5"><w:r><w:t>link</w:t></w:r></w:hyperlink>
+      <w:del><w:r><w:delText>removed</w:delText></w:r></w:del>
+    </w:p>
+    <w:p><w:r><w:t>- dash</w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve"> - mid</w:t></w:r></w:p>
+    <w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>First</w:t></w:r></w:p>
+    <w:p><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Nested</w:t></w:r></w:p>
+    <w:tbl>
+      <w:tr><w:tc><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Score</w:t></w:r></w:p></w:tc></w:tr>
+      <w:tr><w:tc><w:p><w:r><w:t>Ada</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>10</w:t></w:r></w:p></w:tc></w:tr>
+    </w:tbl>
+    <w:sectPr/>
+  </w:body>
+</w:document>"#;
+
+    const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com/" TargetMode="External"/>
+</Relationships>"#;
+
+    #[test]
+    fn test_docx_to_markdown() {
+        let bytes = build(&[
+            ("word/document.xml", DOCUMENT),
+            ("word/_rels/document.xml.rels", RELATIONSHIPS),
+        ]);
+
+        assert_eq!(
+            to_markdown(&bytes).unwrap(),
+            "# Report\n\nPlain, **bold** and a [link](https://example.com/)\n\n\\- dash **- mid**\n\n- First\n  - Nested\n\n| Name | Score |\n| --- | --- |\n| Ada | 10 |"
+        );
+    }
+
+    #[test]
+    fn test_docx_without_document() {
+        let bytes = build(&[("word/styles.xml", "<w:styles/>")]);
+        assert!(to_markdown(&bytes).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// Converts EPUB books to Markdown by converting the XHTML documents of their spine, in reading
+// order, with the local HTML converter.
+
+use std::collections::HashMap;
+
+use super::zip::ZipArchive;
+use crate::{
+    error::{DocumentError, Error},
+    html::dom::parse,
+    types::HtmlToMarkdownOptions,
+};
+
+pub(crate) fn to_markdown(bytes: &[u8]) -> Result<String, Error> {
+    let archive = ZipArchive::new(bytes)?;
+
+    // The container points to the package document, which lists the content of the book.
+    let container = parse(&archive.read_string("META-INF/container.xml")?);
+    let package_path = container
+        .find("rootfile")
+        .and_then(|rootfile| rootfile.attr("full-path"))
+        .ok_or_else(|| {
+            Error::DocumentError(DocumentError::MissingPart {
+                part: "package document".to_string(),
+            })
+        })?;
+    let package = parse(&archive.read_string(package_path)?);
+    let base = package_path.rsplit_once('/').map_or("", |(base, _)| base);
+
+    let manifest = package
+        .find_all("item")
+        .into_iter()
+        .filter_map(|item| Some((item.attr("id")?, item.attr("href")?)))
+        .collect::<HashMap<_, _>>();
+
+    // Chapters are converted as they are, they have no navigation to leave out.
+    let opts = HtmlToMarkdownOptions {
+        extract_main_content: false,
+    };
+
+    let mut chapters = Vec::new();
+    for itemref in package.find_all("itemref") {
+        let Some(href) = itemref.attr("idref").and_then(|id| manifest.get(id)) else {
+            continue;
+        };
+
+        let Ok(xhtml) = archive.read_string(&resolve(base, href)) else {
+            continue;
+        };
+        let markdown = crate::html::to_markdown(&xhtml, &opts);
+        if !markdown.is_empty() {
+            chapters.push(markdown);
+        }
+    }
+
+    Ok(chapters.join("\n\n"))
+}
+
+// Resolves a (percent-encoded) href against the directory of the package document.
+fn resolve(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or_default();
+    let mut segments = base
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    percent_decode(&segments.join("/"))
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let escaped = (byte == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(escaped) => {
+                decoded.push(escaped);
+                i += 3;
+            }
+            None => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::zip::build;
+    use super::*;
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+
+    const PACKAGE: &str = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>A Book</dc:title></metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="c2" href="text/chapter%202.xhtml" media-type="application/xhtml+xml"/>
+    <item id="c1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    <item id="css" href="../styles/book.css" media-type="text/css"/>
+  </manifest>
+  <spine><itemref idref="c1"/><itemref idref="c2"/><itemref idref="missing"/></spine>
+</package>"#;
+
+    #[test]
+    fn test_epub_to_markdown() {
+        let bytes = build(&[
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", CONTAINER),
+            ("OEBPS/content.opf", PACKAGE),
+            (
+                "OEBPS/text/chapter1.xhtml",
+                "<html><head><title>One</title></head><body><h1>Chapter 1</h1><p>It begins.</p></body></html>",
+            ),
+            (
+                "OEBPS/text/chapter 2.xhtml",
+                "<html><body><h1>Chapter 2</h1><p>It <em>ends</em>.</p></body></html>",
+            ),
+        ]);
+
+        assert_eq!(
+            to_markdown(&bytes).unwrap(),
+            "# Chapter 1\n\nIt begins.\n\n# Chapter 2\n\nIt *ends*."
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("OEBPS", "text/a%20b.xhtml#top"),
+            "OEBPS/text/a b.xhtml"
+        );
+        assert_eq!(
+            resolve("OEBPS/text", "../images/c.png"),
+            "OEBPS/images/c.png"
+        );
+        assert_eq!(resolve("", "chapter.xhtml"), "chapter.xhtml");
+    }
+}
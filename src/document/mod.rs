@@ -0,0 +1,167 @@
+// Converts the files behind entries (Office documents, EPUBs, spreadsheets, source code...) to
+// Markdown. Formats that can be read with a bit of XML are converted here, the others (PDFs,
+// legacy Office formats, non-ZIP archives) are left to the host.
+
+mod code;
+mod csv;
+mod docx;
+mod epub;
+mod pptx;
+mod xlsx;
+mod zip;
+
+use std::collections::HashMap;
+
+use crate::{error::Error, types::Type};
+
+/// Converts the document to Markdown, or returns `None` if it can only be converted by the host.
+///
+/// The file name is used to tell apart formats that share a type, e.g. a CSV spreadsheet from an
+/// XLSX one, and to tag code blocks with their language.
+pub(crate) fn to_markdown(
+    bytes: &[u8],
+    kind: &Type,
+    file_name: &str,
+) -> Option<Result<String, Error>> {
+    let is_zip = zip::is_zip(bytes);
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let markdown = match kind {
+        Type::EPUB if is_zip => epub::to_markdown(bytes),
+        Type::WordDocument if is_zip => docx::to_markdown(bytes),
+        Type::Presentation if is_zip => pptx::to_markdown(bytes),
+        Type::Spreadsheet if is_zip => xlsx::to_markdown(bytes),
+        Type::Archive if is_zip => archive_listing(bytes),
+        Type::Spreadsheet | Type::PlainText if matches!(extension.as_str(), "csv" | "tsv") => {
+            Ok(csv::to_markdown(&String::from_utf8_lossy(bytes)))
+        }
+        Type::Code | Type::Interchange => Ok(code::to_markdown(
+            &String::from_utf8_lossy(bytes),
+            file_name,
+        )),
+        Type::Markdown | Type::PlainText => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        _ => return None,
+    };
+
+    Some(markdown)
+}
+
+/// Renders the rows as a Markdown table, with the first row as its header.
+///
+/// Rows are padded to the same number of columns, and pipes and line breaks in cells are escaped
+/// so each row stays on a single line.
+pub(crate) fn table(rows: &[Vec<String>]) -> String {
+    let Some(columns) = rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .filter(|columns| *columns > 0)
+    else {
+        return String::new();
+    };
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (i, row) in rows.iter().enumerate() {
+        let cells = (0..columns)
+            .map(|column| {
+                let cell = row.get(column).map(String::as_str).unwrap_or_default();
+                let cell = cell.split_whitespace().collect::<Vec<_>>().join(" ");
+                cell.replace('|', "\\|")
+            })
+            .collect::<Vec<_>>();
+        lines.push(format!("| {} |", cells.join(" | ")));
+
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Reads the relationships of an Office Open XML part, mapping their ids to their targets.
+fn relationships(archive: &zip::ZipArchive, path: &str) -> HashMap<String, String> {
+    let Ok(xml) = archive.read_string(path) else {
+        return HashMap::new();
+    };
+
+    let mut relationships = HashMap::new();
+    for relationship in crate::html::dom::parse(&xml).find_all("relationship") {
+        if let (Some(id), Some(target)) = (relationship.attr("id"), relationship.attr("target")) {
+            relationships.insert(id.to_string(), target.to_string());
+        }
+    }
+    relationships
+}
+
+// Joins the blocks of a document, keeping consecutive list items together.
+fn join_blocks(blocks: &[(String, bool)]) -> String {
+    let mut markdown = String::new();
+    let mut previous_is_item = false;
+
+    for (block, is_item) in blocks {
+        if !markdown.is_empty() {
+            markdown.push_str(if previous_is_item && *is_item {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        markdown.push_str(block);
+        previous_is_item = *is_item;
+    }
+    markdown
+}
+
+fn archive_listing(bytes: &[u8]) -> Result<String, Error> {
+    let archive = zip::ZipArchive::new(bytes)?;
+    let lines = archive
+        .files()
+        .map(|entry| format!("- `{}` ({} bytes)", entry.name, entry.size))
+        .collect::<Vec<_>>();
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let rows = vec![
+            vec!["a".to_string(), "b|c".to_string()],
+            vec!["1\n2".to_string()],
+        ];
+        assert_eq!(table(&rows), "| a | b\\|c |\n| --- | --- |\n| 1 2 |  |");
+        assert_eq!(table(&[]), "");
+    }
+
+    #[test]
+    fn test_to_markdown_dispatch() {
+        let archive = zip::build(&[("docs/", ""), ("docs/a.txt", "hello")]);
+        assert_eq!(
+            to_markdown(&archive, &Type::Archive, "files.zip")
+                .unwrap()
+                .unwrap(),
+            "- `docs/a.txt` (5 bytes)"
+        );
+        assert_eq!(
+            to_markdown(b"a,b\n1,2", &Type::PlainText, "data.csv")
+                .unwrap()
+                .unwrap(),
+            "| a | b |\n| --- | --- |\n| 1 | 2 |"
+        );
+        assert_eq!(
+            to_markdown(b"SELECT 1;", &Type::Code, "query.sql")
+                .unwrap()
+                .unwrap(),
+            "```sql\nSELECT 1;\n```"
+        );
+        assert!(to_markdown(b"%PDF-1.7", &Type::PDF, "paper.pdf").is_none());
+        assert!(to_markdown(b"\x1f\x8b", &Type::Archive, "files.tar.gz").is_none());
+    }
+}
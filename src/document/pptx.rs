@@ -0,0 +1,202 @@
+// Converts PowerPoint presentations (PPTX) to Markdown, with a section for each slide. The text of
+// the title placeholder becomes the heading, the other text boxes become list items and tables
+// become Markdown tables. Speaker notes are left out.
+
+use super::zip::ZipArchive;
+use crate::{
+    error::Error,
+    html::{
+        dom::{Element, parse},
+        escape, escape_line_starts,
+    },
+};
+
+pub(crate) fn to_markdown(bytes: &[u8]) -> Result<String, Error> {
+    let archive = ZipArchive::new(bytes)?;
+
+    // Slides are numbered in the order they were created, which is a good enough approximation of
+    // their order in the presentation without reading its relationships.
+    let mut slides = archive
+        .files()
+        .filter_map(|entry| {
+            let number = entry
+                .name
+                .strip_prefix("ppt/slides/slide")?
+                .strip_suffix(".xml")?
+                .parse::<usize>()
+                .ok()?;
+            Some((number, entry.name.as_str()))
+        })
+        .collect::<Vec<_>>();
+    slides.sort();
+
+    let mut sections = Vec::new();
+    for (i, (_, path)) in slides.iter().enumerate() {
+        let slide = parse(&archive.read_string(path)?);
+        sections.push(to_section(&slide, i + 1));
+    }
+
+    Ok(sections.join("\n\n"))
+}
+
+fn to_section(slide: &Element, number: usize) -> String {
+    let mut title = None;
+    let mut blocks = Vec::new();
+
+    if let Some(tree) = slide.find("p:sptree") {
+        collect_shapes(tree, &mut title, &mut blocks);
+    }
+
+    let heading = match title {
+        Some(title) => format!("## {title}"),
+        None => format!("## Slide {number}"),
+    };
+    if blocks.is_empty() {
+        heading
+    } else {
+        format!("{heading}\n\n{}", super::join_blocks(&blocks))
+    }
+}
+
+fn collect_shapes(tree: &Element, title: &mut Option<String>, blocks: &mut Vec<(String, bool)>) {
+    for shape in tree.elements() {
+        match shape.name.as_str() {
+            "p:sp" => {
+                let is_title = shape
+                    .find("p:ph")
+                    .and_then(|placeholder| placeholder.attr("type"))
+                    .is_some_and(|kind| matches!(kind, "title" | "ctrTitle"));
+
+                let Some(body) = shape.find("p:txbody") else {
+                    continue;
+                };
+                if is_title && title.is_none() {
+                    let text = paragraphs(body)
+                        .into_iter()
+                        .map(|(_, text)| text)
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if !text.is_empty() {
+                        *title = Some(text);
+                    }
+                    continue;
+                }
+
+                for (level, text) in paragraphs(body) {
+                    blocks.push((format!("{}- {}", "  ".repeat(level), text), true));
+                }
+            }
+            "p:graphicframe" => {
+                if let Some(table) = shape.find("a:tbl") {
+                    let table = table_to_markdown(table);
+                    if !table.is_empty() {
+                        blocks.push((table, false));
+                    }
+                }
+            }
+            "p:grpsp" => collect_shapes(shape, title, blocks),
+            _ => (),
+        }
+    }
+}
+
+// Returns the non-empty paragraphs of a text body, with their indentation level.
+fn paragraphs(body: &Element) -> Vec<(usize, String)> {
+    body.elements()
+        .filter(|paragraph| paragraph.name == "a:p")
+        .filter_map(|paragraph| {
+            let level = paragraph
+                .elements()
+                .find(|child| child.name == "a:ppr")
+                .and_then(|properties| properties.attr("lvl"))
+                .and_then(|level| level.parse().ok())
+                .unwrap_or(0);
+
+            let text = paragraph_text(paragraph);
+            (!text.is_empty()).then_some((level, text))
+        })
+        .collect()
+}
+
+fn paragraph_text(paragraph: &Element) -> String {
+    let mut text = String::new();
+    for child in paragraph.elements() {
+        match child.name.as_str() {
+            "a:r" | "a:fld" => {
+                text.push_str(&child.find("a:t").map(Element::text).unwrap_or_default())
+            }
+            "a:br" => text.push(' '),
+            _ => (),
+        }
+    }
+
+    escape_line_starts(&escape(
+        &text.split_whitespace().collect::<Vec<_>>().join(" "),
+    ))
+}
+
+fn table_to_markdown(table: &Element) -> String {
+    let rows = table
+        .elements()
+        .filter(|row| row.name == "a:tr")
+        .map(|row| {
+            row.elements()
+                .filter(|cell| cell.name == "a:tc")
+                .map(|cell| {
+                    cell.find_all("a:p")
+                        .into_iter()
+                        .map(paragraph_text)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    super::table(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::zip::build;
+    use super::*;
+
+    fn slide(shapes: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+  <p:cSld><p:spTree><p:nvGrpSpPr/>{shapes}</p:spTree></p:cSld>
+</p:sld>"#
+        )
+    }
+
+    #[test]
+    fn test_pptx_to_markdown() {
+        let first = slide(
+            r#"<p:sp><p:nvSpPr><p:nvPr><p:ph type="ctrTitle"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>Quarterly review</a:t></a:r></a:p></p:txBody></p:sp>
+<p:sp><p:nvSpPr><p:nvPr><p:ph idx="1"/></p:nvPr></p:nvSpPr><p:txBody>
+  <a:p><a:r><a:t>Revenue </a:t></a:r><a:r><a:rPr b="1"/><a:t>up</a:t></a:r></a:p>
+  <a:p><a:pPr lvl="1"/><a:r><a:t>Mostly *new* customers</a:t></a:r></a:p>
+  <a:p/>
+</p:txBody></p:sp>"#,
+        );
+        let second = slide(
+            r#"<p:graphicFrame><a:graphic><a:graphicData><a:tbl>
+  <a:tr><a:tc><a:txBody><a:p><a:r><a:t>Q1</a:t></a:r></a:p></a:txBody></a:tc><a:tc><a:txBody><a:p><a:r><a:t>Q2</a:t></a:r></a:p></a:txBody></a:tc></a:tr>
+  <a:tr><a:tc><a:txBody><a:p><a:r><a:t>10</a:t></a:r></a:p></a:txBody></a:tc><a:tc><a:txBody><a:p><a:r><a:t>12</a:t></a:r></a:p></a:txBody></a:tc></a:tr>
+</a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#,
+        );
+
+        let bytes = build(&[
+            ("ppt/presentation.xml", "<p:presentation/>"),
+            ("ppt/slides/slide10.xml", &slide("")),
+            ("ppt/slides/slide2.xml", &second),
+            ("ppt/slides/slide1.xml", &first),
+        ]);
+
+        assert_eq!(
+            to_markdown(&bytes).unwrap(),
+            "## Quarterly review\n\n- Revenue up\n  - Mostly \\*new\\* customers\n\n## Slide 2\n\n| Q1 | Q2 |\n| --- | --- |\n| 10 | 12 |\n\n## Slide 3"
+        );
+    }
+}
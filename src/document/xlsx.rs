@@ -0,0 +1,196 @@
+// Converts Excel workbooks (XLSX) to Markdown, with a table for each sheet. Cells hold their
+// stored values: formulas are not evaluated and number formats (dates, percentages...) are not
+// applied.
+
+use super::{relationships, zip::ZipArchive};
+use crate::{
+    error::{DocumentError, Error},
+    html::dom::{Element, parse},
+};
+
+// The number of columns in a sheet, up to `XFD`.
+const MAX_COLUMNS: usize = 16_384;
+
+pub(crate) fn to_markdown(bytes: &[u8]) -> Result<String, Error> {
+    let archive = ZipArchive::new(bytes)?;
+    let workbook = parse(&archive.read_string("xl/workbook.xml")?);
+    let targets = relationships(&archive, "xl/_rels/workbook.xml.rels");
+    let shared_strings = archive
+        .read_string("xl/sharedStrings.xml")
+        .map(|xml| {
+            parse(&xml)
+                .find_all("si")
+                .into_iter()
+                .map(text)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut sections = Vec::new();
+    for sheet in workbook.find_all("sheet") {
+        let name = sheet.attr("name").unwrap_or("Sheet");
+        let target = sheet
+            .attr("r:id")
+            .and_then(|id| targets.get(id))
+            .ok_or_else(|| {
+                Error::DocumentError(DocumentError::MissingPart {
+                    part: format!("sheet {name}"),
+                })
+            })?;
+
+        // Targets are relative to the workbook, unless they are absolute within the package.
+        let path = match target.strip_prefix('/') {
+            Some(path) => path.to_string(),
+            None => format!("xl/{target}"),
+        };
+
+        let rows = rows(&parse(&archive.read_string(&path)?), &shared_strings);
+        if !rows.is_empty() {
+            sections.push(format!("## {}\n\n{}", name, super::table(&rows)));
+        }
+    }
+
+    Ok(sections.join("\n\n"))
+}
+
+fn rows(sheet: &Element, shared_strings: &[String]) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+
+    for row in sheet.find_all("row") {
+        let mut cells = Vec::new();
+        for cell in row.elements().filter(|cell| cell.name == "c") {
+            // Empty cells are usually left out, so the reference tells which column this is.
+            let column = match cell.attr("r").map(column_index) {
+                Some(Some(column)) => column,
+                // A malformed reference or one past the last column, the cell can't be placed.
+                Some(None) => continue,
+                None => cells.len(),
+            };
+            if column >= cells.len() {
+                cells.resize(column + 1, String::new());
+            }
+            cells[column] = value(cell, shared_strings);
+        }
+
+        if cells.iter().any(|cell| !cell.trim().is_empty()) {
+            rows.push(cells);
+        }
+    }
+
+    rows
+}
+
+fn value(cell: &Element, shared_strings: &[String]) -> String {
+    let raw = || {
+        cell.elements()
+            .find(|child| child.name == "v")
+            .map(Element::text)
+            .unwrap_or_default()
+    };
+
+    match cell.attr("t").unwrap_or_default() {
+        "s" => raw()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| shared_strings.get(i).cloned())
+            .unwrap_or_default(),
+        "inlineStr" => cell
+            .elements()
+            .find(|child| child.name == "is")
+            .map(text)
+            .unwrap_or_default(),
+        "b" => match raw().trim() {
+            "1" => "TRUE".to_string(),
+            _ => "FALSE".to_string(),
+        },
+        _ => raw(),
+    }
+}
+
+// The text of a rich text element, leaving out phonetic hints.
+fn text(element: &Element) -> String {
+    element
+        .elements()
+        .filter(|child| child.name != "rph")
+        .map(|child| match child.name.as_str() {
+            "t" => child.text(),
+            _ => text(child),
+        })
+        .collect()
+}
+
+// Returns the zero-based column of a cell reference, e.g. 27 for `AB12`, or `None` if it is past
+// the last column of a sheet (`XFD`).
+fn column_index(reference: &str) -> Option<usize> {
+    let letters = reference
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>();
+    if letters.is_empty() {
+        return None;
+    }
+
+    let number = letters.chars().try_fold(0usize, |number, letter| {
+        number
+            .checked_mul(26)?
+            .checked_add(letter.to_ascii_uppercase() as usize - 'A' as usize + 1)
+    })?;
+    (number <= MAX_COLUMNS).then(|| number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::zip::build;
+    use super::*;
+
+    const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <sheets>
+    <sheet name="Scores" sheetId="1" r:id="rId1"/>
+    <sheet name="Empty" sheetId="2" r:id="rId2"/>
+  </sheets>
+</workbook>"#;
+
+    const RELATIONSHIPS: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="/xl/worksheets/sheet2.xml"/>
+</Relationships>"#;
+
+    const SHARED_STRINGS: &str = r#"<sst><si><t>Name</t></si><si><t>Score</t></si><si><r><t>Ada </t></r><r><rPr><b/></rPr><t>Lovelace</t></r></si></sst>"#;
+
+    const SHEET: &str = r#"<worksheet><sheetData>
+  <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1" t="inlineStr"><is><t>Passed</t></is></c></row>
+  <row r="3"><c r="A3" t="s"><v>2</v></c><c r="B3"><f>SUM(1,2)</f><v>3</v></c><c r="C3" t="b"><v>1</v></c></row>
+  <row r="4"><c r="C4"><v>0.5</v></c></row>
+</sheetData></worksheet>"#;
+
+    #[test]
+    fn test_xlsx_to_markdown() {
+        let bytes = build(&[
+            ("xl/workbook.xml", WORKBOOK),
+            ("xl/_rels/workbook.xml.rels", RELATIONSHIPS),
+            ("xl/sharedStrings.xml", SHARED_STRINGS),
+            ("xl/worksheets/sheet1.xml", SHEET),
+            (
+                "xl/worksheets/sheet2.xml",
+                "<worksheet><sheetData/></worksheet>",
+            ),
+        ]);
+
+        assert_eq!(
+            to_markdown(&bytes).unwrap(),
+            "## Scores\n\n| Name | Score | Passed |\n| --- | --- | --- |\n| Ada Lovelace | 3 | TRUE |\n|  |  | 0.5 |"
+        );
+    }
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("AB12"), Some(27));
+        assert_eq!(column_index("12"), None);
+        assert_eq!(column_index("XFD1"), Some(16_383));
+        assert_eq!(column_index("XFE1"), None);
+        assert_eq!(column_index(&"Z".repeat(100)), None);
+    }
+}
@@ -0,0 +1,279 @@
+// A minimal ZIP reader, enough to read the parts of Office Open XML documents and EPUBs. Only
+// stored and deflated entries are supported, and ZIP64 archives are not.
+
+use crate::error::{DocumentError, Error};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+// Entries larger than this are not extracted, to protect against zip bombs.
+const MAX_ENTRY_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ZipEntry {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+    method: u16,
+    compressed_size: usize,
+    header_offset: usize,
+}
+
+pub(crate) struct ZipArchive<'a> {
+    bytes: &'a [u8],
+    pub(crate) entries: Vec<ZipEntry>,
+}
+
+/// Whether the bytes look like a ZIP archive.
+pub(crate) fn is_zip(bytes: &[u8]) -> bool {
+    bytes.len() >= 4 && read_u32(bytes, 0) == Some(LOCAL_HEADER_SIGNATURE)
+}
+
+impl<'a> ZipArchive<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Result<Self, Error> {
+        let end =
+            find_end_of_central_directory(bytes).ok_or_else(|| invalid("no central directory"))?;
+        let count = read_u16(bytes, end + 10).ok_or_else(|| invalid("truncated"))? as usize;
+        let mut offset = read_u32(bytes, end + 16).ok_or_else(|| invalid("truncated"))? as usize;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if read_u32(bytes, offset) != Some(CENTRAL_HEADER_SIGNATURE) {
+                return Err(invalid("bad central directory header"));
+            }
+
+            let u16_field =
+                |at: usize| read_u16(bytes, add(offset, at)?).ok_or_else(|| invalid("truncated"));
+            let u32_field =
+                |at: usize| read_u32(bytes, add(offset, at)?).ok_or_else(|| invalid("truncated"));
+            let method = u16_field(10)?;
+            let name_length = u16_field(28)? as usize;
+            let extra_length = u16_field(30)? as usize;
+            let comment_length = u16_field(32)? as usize;
+            let compressed_size = u32_field(20)? as usize;
+            let size = u32_field(24)? as u64;
+            let header_offset = u32_field(42)? as usize;
+            let name_start = add(offset, 46)?;
+            let name = bytes
+                .get(name_start..add(name_start, name_length)?)
+                .ok_or_else(|| invalid("truncated"))?;
+
+            entries.push(ZipEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                size,
+                method,
+                compressed_size,
+                header_offset,
+            });
+            offset = add(
+                add(add(name_start, name_length)?, extra_length)?,
+                comment_length,
+            )?;
+        }
+
+        Ok(ZipArchive { bytes, entries })
+    }
+
+    /// Returns the names of all the files in the archive (directories excluded).
+    pub(crate) fn files(&self) -> impl Iterator<Item = &ZipEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.name.ends_with('/'))
+    }
+
+    /// Reads and decompresses the file with the given name.
+    pub(crate) fn read(&self, name: &str) -> Result<Vec<u8>, Error> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| {
+                Error::DocumentError(DocumentError::MissingPart {
+                    part: name.to_string(),
+                })
+            })?;
+
+        let offset = entry.header_offset;
+        if read_u32(self.bytes, offset) != Some(LOCAL_HEADER_SIGNATURE) {
+            return Err(invalid("bad local file header"));
+        }
+        let field =
+            |at: usize| read_u16(self.bytes, add(offset, at)?).ok_or_else(|| invalid("truncated"));
+        let name_length = field(26)? as usize;
+        let extra_length = field(28)? as usize;
+        let start = add(add(add(offset, 30)?, name_length)?, extra_length)?;
+        let data = self
+            .bytes
+            .get(start..add(start, entry.compressed_size)?)
+            .ok_or_else(|| invalid("truncated"))?;
+
+        match entry.method {
+            STORED => Ok(data.to_vec()),
+            DEFLATED => miniz_oxide::inflate::decompress_to_vec_with_limit(data, MAX_ENTRY_SIZE)
+                .map_err(|_| invalid(&format!("failed to inflate {}", entry.name))),
+            method => Err(Error::DocumentError(
+                DocumentError::UnsupportedCompression {
+                    name: entry.name.clone(),
+                    method,
+                },
+            )),
+        }
+    }
+
+    /// Reads the file with the given name as (lossy) UTF-8 text.
+    pub(crate) fn read_string(&self, name: &str) -> Result<String, Error> {
+        let bytes = self.read(name)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+fn find_end_of_central_directory(bytes: &[u8]) -> Option<usize> {
+    // The record is at the very end of the archive, followed by a comment of up to 64 KiB.
+    let last = bytes.len().checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)?;
+    let first = last.saturating_sub(u16::MAX as usize);
+    (first..=last)
+        .rev()
+        .find(|offset| read_u32(bytes, *offset) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// Offsets and lengths are read from the archive, so adding them may overflow on corrupt archives.
+fn add(offset: usize, length: usize) -> Result<usize, Error> {
+    offset
+        .checked_add(length)
+        .ok_or_else(|| invalid("offset out of range"))
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::DocumentError(DocumentError::InvalidArchive {
+        reason: reason.to_string(),
+    })
+}
+
+/// Builds a ZIP archive from the given files, deflating every other one so both methods are
+/// covered. CRCs are left empty as they are not checked when reading.
+#[cfg(test)]
+pub(crate) fn build(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (i, (name, content)) in files.iter().enumerate() {
+        let (method, data) = if i % 2 == 0 {
+            (STORED, content.as_bytes().to_vec())
+        } else {
+            (
+                DEFLATED,
+                miniz_oxide::deflate::compress_to_vec(content.as_bytes(), 6),
+            )
+        };
+
+        let offset = archive.len() as u32;
+        archive.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[20, 0, 0, 0]);
+        archive.extend_from_slice(&method.to_le_bytes());
+        archive.extend_from_slice(&[0; 8]);
+        archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&data);
+
+        central_directory.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+        central_directory.extend_from_slice(&method.to_le_bytes());
+        central_directory.extend_from_slice(&[0; 8]);
+        central_directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let central_directory_offset = archive.len() as u32;
+    archive.extend_from_slice(&central_directory);
+    archive.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]);
+    archive.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    archive.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&central_directory_offset.to_le_bytes());
+    archive.extend_from_slice(&[0, 0]);
+    archive
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_archive() {
+        let bytes = build(&[
+            ("a.txt", "stored"),
+            ("dir/b.txt", "deflated deflated deflated"),
+        ]);
+        assert!(is_zip(&bytes));
+
+        let archive = ZipArchive::new(&bytes).unwrap();
+        let names = archive.files().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["a.txt", "dir/b.txt"]);
+        assert_eq!(archive.read_string("a.txt").unwrap(), "stored");
+        assert_eq!(
+            archive.read_string("dir/b.txt").unwrap(),
+            "deflated deflated deflated"
+        );
+        assert!(archive.read("missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_invalid_archive() {
+        assert!(!is_zip(b"not a zip"));
+        assert!(ZipArchive::new(b"not a zip").is_err());
+        assert!(ZipArchive::new(&build(&[("a", "b")])[..40]).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_central_directory() {
+        let bytes = build(&[("a.txt", "stored")]);
+        let central_directory = bytes.len() - END_OF_CENTRAL_DIRECTORY_SIZE - 46 - "a.txt".len();
+
+        // A name length pointing past the end of the archive.
+        let mut corrupt = bytes.clone();
+        corrupt[central_directory + 28..central_directory + 30]
+            .copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(ZipArchive::new(&corrupt).is_err());
+
+        // A compressed size or header offset pointing past the end of the archive, which overflow
+        // on 32-bit targets.
+        for field in [20, 42] {
+            let mut corrupt = bytes.clone();
+            corrupt[central_directory + field..central_directory + field + 4]
+                .copy_from_slice(&u32::MAX.to_le_bytes());
+            let archive = ZipArchive::new(&corrupt).unwrap();
+            assert!(matches!(
+                archive.read("a.txt"),
+                Err(Error::DocumentError(DocumentError::InvalidArchive { .. }))
+            ));
+        }
+
+        // A central directory offset past the end of the archive.
+        let mut corrupt = bytes;
+        let end = corrupt.len() - END_OF_CENTRAL_DIRECTORY_SIZE;
+        corrupt[end + 16..end + 20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ZipArchive::new(&corrupt).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     allocator,
     error::{self, Error},
-    host, safe_alloc, types,
+    host, safe_alloc,
+    transform::{self, ChunkStrategy},
+    types,
 };
 
 /// Update an entry in the data store.
@@ -37,3 +39,33 @@ pub fn create_chunks(opts: types::CreateChunksOpts) -> Result<u64, error::Error>
     let count = unsafe { host::entry_create_chunks(ptr, size) };
     Ok(count)
 }
+
+/// Chunks an entry and creates the resulting chunks for it in one go.
+///
+/// The entry's plain text is chunked with the given strategy (its Markdown without front matter
+/// for [`ChunkStrategy::LocalMarkdown`]), each chunk is numbered in order, tagged with its
+/// detected language and the entry's current version, and then created with [`create_chunks`].
+///
+/// Returns the number of chunks created.
+pub fn index_chunks(entry: &types::Entry, strategy: ChunkStrategy) -> Result<u64, error::Error> {
+    let text = match strategy {
+        ChunkStrategy::LocalMarkdown(_) => crate::front_matter::split(&entry.markdown).1,
+        _ => &entry.plain_text,
+    };
+
+    if text.trim().is_empty() {
+        return Ok(0);
+    }
+
+    let chunks = transform::chunk(text, &strategy)?
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| chunk.to_new_chunk(&entry.id, index as i32, entry.version))
+        .collect::<Vec<_>>();
+
+    if chunks.is_empty() {
+        return Ok(0);
+    }
+
+    create_chunks(types::CreateChunksOpts { chunks })
+}
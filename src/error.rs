@@ -22,6 +22,9 @@ pub enum Error {
     #[error("No valid UTF-8 string found in memory")]
     EmptyString,
 
+    #[error("No content found in {context}: {reason}")]
+    NoContent { context: String, reason: String },
+
     #[error("A memory-related error occured: {0}")]
     MemoryError(String),
 
@@ -42,6 +45,15 @@ pub enum Error {
 
     #[error("Invalid rand value size: {expected} bytes, got {actual} bytes")]
     BadRandomSize { expected: u32, actual: u32 },
+
+    #[error("{0}")]
+    CryptoError(CryptoError),
+
+    #[error("Failed to write Markdown: {0}")]
+    Markdown(pulldown_cmark_to_cmark::Error),
+
+    #[error("{0}")]
+    DocumentError(DocumentError),
 }
 
 #[derive(Debug, Error)]
@@ -56,6 +68,42 @@ pub enum StoreError {
     FailedDelete { key: String, reason: String },
 }
 
+#[derive(Debug, Error)]
+pub enum CryptoError {
+    #[error("Signature is missing or malformed")]
+    MalformedSignature,
+
+    #[error("Signature does not match the payload")]
+    SignatureMismatch,
+
+    #[error("Signature timestamp {timestamp} is outside the tolerance of {tolerance} seconds")]
+    TimestampOutOfTolerance { timestamp: u64, tolerance: u64 },
+
+    #[error("Invalid key length: expected {expected} bytes, got {actual} bytes")]
+    InvalidKeyLength { expected: usize, actual: usize },
+
+    #[error("Failed to encrypt data")]
+    EncryptionFailed,
+
+    #[error("Failed to decrypt data, it may have been tampered with or sealed with another key")]
+    DecryptionFailed,
+}
+
+#[derive(Debug, Error)]
+pub enum DocumentError {
+    #[error("Invalid archive: {reason}")]
+    InvalidArchive { reason: String },
+
+    #[error("Unsupported compression method {method} for {name}")]
+    UnsupportedCompression { name: String, method: u16 },
+
+    #[error("Missing {part} in the document")]
+    MissingPart { part: String },
+
+    #[error("Failed to fetch the document, status code: {status}")]
+    FetchFailed { status: i32 },
+}
+
 impl Error {
     /// Writes an error string to a shared memory space and returns an encoded pointer.
     pub fn write_to_host(&self) -> u64 {
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::types::{FrontMatter, FrontMatterFormat, FrontMatterValue};
+
+/// Splits the front matter off the top of the Markdown, returning it along with the rest of the
+/// document.
+///
+/// Front matter is a YAML block between `---` lines (closed by `---` or `...`) or a TOML block
+/// between `+++` lines, which must start on the first line of the document. Blocks that don't
+/// parse to a map of fields are not front matter (e.g. a thematic break followed by a setext
+/// heading) and are left in the document.
+pub fn split(markdown: &str) -> (Option<FrontMatter>, &str) {
+    match try_split(markdown) {
+        Some((front_matter, body)) => (Some(front_matter), body),
+        None => (None, markdown),
+    }
+}
+
+fn try_split(markdown: &str) -> Option<(FrontMatter, &str)> {
+    let text = markdown.strip_prefix('\u{feff}').unwrap_or(markdown);
+    let mut lines = text.split_inclusive('\n');
+
+    let (format, closing): (_, &[&str]) = match lines.next()?.trim_end() {
+        "---" => (FrontMatterFormat::Yaml, &["---", "..."]),
+        "+++" => (FrontMatterFormat::Toml, &["+++"]),
+        _ => return None,
+    };
+
+    let start = text.len() - lines.clone().map(str::len).sum::<usize>();
+    let mut end = start;
+    loop {
+        let line = lines.next()?;
+        if closing.contains(&line.trim_end()) {
+            break;
+        }
+        end += line.len();
+    }
+
+    let source = &text[start..end];
+    let fields = match format {
+        FrontMatterFormat::Yaml => parse_yaml(source)?,
+        FrontMatterFormat::Toml => parse_toml(source)?,
+    };
+
+    let body = &text[text.len() - lines.map(str::len).sum::<usize>()..];
+    Some((FrontMatter { format, fields }, body))
+}
+
+fn parse_yaml(source: &str) -> Option<BTreeMap<String, FrontMatterValue>> {
+    let mut documents = YamlLoader::load_from_str(source).ok()?;
+    if documents.is_empty() {
+        return Some(BTreeMap::new());
+    }
+
+    match from_yaml(documents.swap_remove(0)) {
+        FrontMatterValue::Map(fields) => Some(fields),
+        FrontMatterValue::Null => Some(BTreeMap::new()),
+        _ => None,
+    }
+}
+
+fn from_yaml(yaml: Yaml) -> FrontMatterValue {
+    match yaml {
+        Yaml::Boolean(value) => FrontMatterValue::Bool(value),
+        Yaml::Integer(value) => FrontMatterValue::Integer(value),
+        Yaml::Real(value) => match value.parse() {
+            Ok(value) => FrontMatterValue::Float(value),
+            Err(_) => FrontMatterValue::String(value),
+        },
+        Yaml::String(value) => FrontMatterValue::String(value),
+        Yaml::Array(values) => FrontMatterValue::List(values.into_iter().map(from_yaml).collect()),
+        Yaml::Hash(entries) => FrontMatterValue::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (yaml_key(key), from_yaml(value)))
+                .collect(),
+        ),
+        Yaml::Null | Yaml::Alias(_) | Yaml::BadValue => FrontMatterValue::Null,
+    }
+}
+
+// YAML allows any value as a key, scalars are stringified and anything else is dropped into an
+// empty key.
+fn yaml_key(key: Yaml) -> String {
+    match key {
+        Yaml::String(key) | Yaml::Real(key) => key,
+        Yaml::Integer(key) => key.to_string(),
+        Yaml::Boolean(key) => key.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn parse_toml(source: &str) -> Option<BTreeMap<String, FrontMatterValue>> {
+    let table = source.parse::<toml::Table>().ok()?;
+    Some(
+        table
+            .into_iter()
+            .map(|(key, value)| (key, from_toml(value)))
+            .collect(),
+    )
+}
+
+fn from_toml(value: toml::Value) -> FrontMatterValue {
+    match value {
+        toml::Value::Boolean(value) => FrontMatterValue::Bool(value),
+        toml::Value::Integer(value) => FrontMatterValue::Integer(value),
+        toml::Value::Float(value) => FrontMatterValue::Float(value),
+        toml::Value::String(value) => FrontMatterValue::String(value),
+        toml::Value::Datetime(value) => FrontMatterValue::String(value.to_string()),
+        toml::Value::Array(values) => {
+            FrontMatterValue::List(values.into_iter().map(from_toml).collect())
+        }
+        toml::Value::Table(table) => FrontMatterValue::Map(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, from_toml(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let markdown =
+            "---\ntitle: Hello World\ntags:\n  - rust\n  - wasm\ndraft: false\n---\n# Hello\n";
+        let (front_matter, body) = split(markdown);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.format, FrontMatterFormat::Yaml);
+        assert_eq!(front_matter.title(), Some("Hello World"));
+        assert_eq!(
+            front_matter.get("tags"),
+            Some(&FrontMatterValue::List(vec![
+                FrontMatterValue::String("rust".to_string()),
+                FrontMatterValue::String("wasm".to_string()),
+            ]))
+        );
+        assert_eq!(
+            front_matter.get("draft"),
+            Some(&FrontMatterValue::Bool(false))
+        );
+        assert_eq!(body, "# Hello\n");
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let markdown = "+++\ntitle = \"Hello\"\ndate = 2024-01-02\nweight = 3\n+++\n\nBody";
+        let (front_matter, body) = split(markdown);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.format, FrontMatterFormat::Toml);
+        assert_eq!(front_matter.title(), Some("Hello"));
+        assert_eq!(
+            front_matter.get("date"),
+            Some(&FrontMatterValue::String("2024-01-02".to_string()))
+        );
+        assert_eq!(
+            front_matter.get("weight"),
+            Some(&FrontMatterValue::Integer(3))
+        );
+        assert_eq!(body, "\nBody");
+    }
+
+    #[test]
+    fn test_empty_front_matter() {
+        let (front_matter, body) = split("---\n---\nBody");
+        assert!(front_matter.unwrap().fields.is_empty());
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_no_front_matter() {
+        for markdown in [
+            "# Hello\n\n---\ntitle: nope\n---\n",
+            "---\nunclosed: true\n",
+            "---\nJust a setext heading\n---\n",
+            "Body",
+            "",
+        ] {
+            let (front_matter, body) = split(markdown);
+            assert!(front_matter.is_none(), "{:?}", markdown);
+            assert_eq!(body, markdown);
+        }
+    }
+}
@@ -12,6 +12,9 @@ unsafe extern "C" {
     #[link_name = "chunk_with_overlap"]
     pub(crate) fn chunk_with_overlap(ptr: u32, size: u32) -> u64;
 
+    #[link_name = "chunk_with_options"]
+    pub(crate) fn chunk_with_options(ptr: u32, size: u32) -> u64;
+
     #[link_name = "chunk_by_sentence"]
     pub(crate) fn chunk_by_sentence(ptr: u32, size: u32) -> u64;
 
@@ -24,6 +27,12 @@ unsafe extern "C" {
     #[link_name = "transform_html_to_markdown"]
     pub(crate) fn transform_html_to_markdown(ptr: u32, size: u32) -> u64;
 
+    #[link_name = "transform_extract_article"]
+    pub(crate) fn transform_extract_article(ptr: u32, size: u32) -> u64;
+
+    #[link_name = "transform_document_to_markdown"]
+    pub(crate) fn transform_document_to_markdown(ptr: u32, size: u32) -> u64;
+
     #[link_name = "entry_update"]
     pub(crate) fn entry_update(ptr: u32, size: u32) -> u64;
 
@@ -49,4 +58,11 @@ unsafe extern "C" {
     // Crypto
     #[link_name = "crypto_rand"]
     pub(crate) fn crypto_rand(ptr: u32, size: u32) -> u64;
+
+    #[link_name = "crypto_plugin_key"]
+    pub(crate) fn crypto_plugin_key(ptr: u32, size: u32) -> u64;
+
+    // Time
+    #[link_name = "time_now"]
+    pub(crate) fn time_now(ptr: u32, size: u32) -> u64;
 }
@@ -0,0 +1,273 @@
+// Finds the main content of a web page, leaving out navigation, sidebars, footers, cookie banners
+// and the like. The scoring is a simplified version of the one used by Readability: text-heavy
+// paragraphs add to the score of their parent (and half of it to their grandparent), and the
+// highest scoring element is the content.
+
+use std::collections::HashMap;
+
+use super::dom::{Element, Node};
+
+// Elements that never contain content worth converting.
+const REMOVED_ELEMENTS: &[&str] = &[
+    "button", "canvas", "embed", "head", "iframe", "input", "noscript", "object", "script",
+    "select", "style", "svg", "template", "textarea",
+];
+
+const NOISE_ELEMENTS: &[&str] = &["aside", "footer", "nav"];
+
+const NOISE_ROLES: &[&str] = &[
+    "banner",
+    "complementary",
+    "contentinfo",
+    "dialog",
+    "menu",
+    "navigation",
+];
+
+// Class and id tokens of elements that are not part of the content.
+const NOISE_TOKENS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "consent",
+    "cookie",
+    "cookies",
+    "footer",
+    "menu",
+    "modal",
+    "nav",
+    "navbar",
+    "navigation",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sharing",
+    "sidebar",
+    "social",
+    "sponsored",
+    "subscribe",
+    "widget",
+];
+
+// Class and id tokens of elements that are likely to hold the content.
+const CONTENT_TOKENS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "post", "story", "text",
+];
+
+// Elements that are never removed as noise, even if their class or id looks like it.
+const CONTENT_ELEMENTS: &[&str] = &["#document", "html", "body", "main", "article"];
+
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+// Content shorter than this is not trusted and the whole body is used instead.
+const MIN_CONTENT_LENGTH: usize = 140;
+
+/// Removes elements that can't be converted (scripts, styles, forms controls...).
+pub(crate) fn strip(element: &Element) -> Element {
+    filter(element, &|child| {
+        !REMOVED_ELEMENTS.contains(&child.name.as_str())
+    })
+}
+
+/// Returns the element holding the main content of the document.
+pub(crate) fn extract(document: &Element) -> Element {
+    let document = filter(document, &|child| {
+        !REMOVED_ELEMENTS.contains(&child.name.as_str()) && !is_noise(child)
+    });
+    let body = document.find("body").unwrap_or(&document);
+
+    // Pages that mark up their content are trusted, the largest block wins.
+    let mut marked = Vec::new();
+    collect(body, &mut marked, &|element| {
+        matches!(element.name.as_str(), "article" | "main") || element.attr("role") == Some("main")
+    });
+    if let Some(content) = marked
+        .into_iter()
+        .map(|element| (text_length(element), element))
+        .filter(|(length, _)| *length >= MIN_CONTENT_LENGTH)
+        .max_by_key(|(length, _)| *length)
+        .map(|(_, element)| element)
+    {
+        return content.clone();
+    }
+
+    let mut scores = HashMap::new();
+    score(body, &mut Vec::new(), &mut scores);
+
+    let best = scores
+        .into_iter()
+        .filter_map(|(path, score)| {
+            let element = at(body, &path)?;
+            let bonus = if has_token(element, CONTENT_TOKENS) {
+                1.25
+            } else {
+                1.0
+            };
+            Some((score * bonus * (1.0 - link_density(element)), element))
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, element)| element);
+
+    match best {
+        Some(element) if text_length(element) >= MIN_CONTENT_LENGTH => element.clone(),
+        _ => body.clone(),
+    }
+}
+
+fn filter(element: &Element, keep: &dyn Fn(&Element) -> bool) -> Element {
+    Element {
+        name: element.name.clone(),
+        attrs: element.attrs.clone(),
+        children: element
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Element(child) if !keep(child) => None,
+                Node::Element(child) => Some(Node::Element(filter(child, keep))),
+                Node::Text(text) => Some(Node::Text(text.clone())),
+            })
+            .collect(),
+    }
+}
+
+fn is_noise(element: &Element) -> bool {
+    if CONTENT_ELEMENTS.contains(&element.name.as_str()) {
+        return false;
+    }
+
+    let is_hidden = element.attr("hidden").is_some()
+        || element.attr("aria-hidden") == Some("true")
+        || element.attr("style").is_some_and(|style| {
+            let style = style.replace(' ', "").to_ascii_lowercase();
+            style.contains("display:none") || style.contains("visibility:hidden")
+        });
+
+    is_hidden
+        || NOISE_ELEMENTS.contains(&element.name.as_str())
+        || element
+            .attr("role")
+            .is_some_and(|role| NOISE_ROLES.contains(&role))
+        || (has_token(element, NOISE_TOKENS) && !has_token(element, CONTENT_TOKENS))
+}
+
+fn has_token(element: &Element, tokens: &[&str]) -> bool {
+    ["class", "id"]
+        .iter()
+        .filter_map(|name| element.attr(name))
+        .flat_map(|value| value.split(|c: char| !c.is_ascii_alphanumeric()))
+        .any(|token| tokens.contains(&token.to_ascii_lowercase().as_str()))
+}
+
+fn collect<'a>(
+    element: &'a Element,
+    found: &mut Vec<&'a Element>,
+    predicate: &dyn Fn(&Element) -> bool,
+) {
+    for child in element.elements() {
+        if predicate(child) {
+            found.push(child);
+        }
+        collect(child, found, predicate);
+    }
+}
+
+// Scores the parents of paragraphs, keyed by the path of child indexes from the body.
+fn score(element: &Element, path: &mut Vec<usize>, scores: &mut HashMap<Vec<usize>, f64>) {
+    for (i, child) in element.children.iter().enumerate() {
+        let Node::Element(child) = child else {
+            continue;
+        };
+
+        path.push(i);
+        if matches!(child.name.as_str(), "p" | "pre" | "td" | "blockquote") {
+            let text = child.text();
+            let length = text.trim().chars().count();
+            if length >= MIN_PARAGRAPH_LENGTH {
+                let points = 1.0 + text.matches(',').count() as f64 + (length / 100).min(3) as f64;
+                let parent = &path[..path.len() - 1];
+                *scores.entry(parent.to_vec()).or_default() += points;
+                if let Some((_, grandparent)) = parent.split_last() {
+                    *scores.entry(grandparent.to_vec()).or_default() += points / 2.0;
+                }
+            }
+        }
+        score(child, path, scores);
+        path.pop();
+    }
+}
+
+fn at<'a>(element: &'a Element, path: &[usize]) -> Option<&'a Element> {
+    match path.split_first() {
+        None => Some(element),
+        Some((i, rest)) => match element.children.get(*i)? {
+            Node::Element(child) => at(child, rest),
+            Node::Text(_) => None,
+        },
+    }
+}
+
+fn text_length(element: &Element) -> usize {
+    element.text().split_whitespace().map(str::len).sum()
+}
+
+// The share of the element's text that is inside links.
+fn link_density(element: &Element) -> f64 {
+    let total = text_length(element);
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut links = Vec::new();
+    collect(element, &mut links, &|child| child.name == "a");
+    let linked = links.iter().map(|link| text_length(link)).sum::<usize>();
+    linked as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dom::parse;
+    use super::*;
+
+    const PARAGRAPH: &str = "This paragraph is long enough to count as content, with a few commas, clauses and words in it.";
+
+    #[test]
+    fn test_extract_marked_content() {
+        let html = format!(
+            "<body><nav><a href=\"/\">Home</a></nav><article><h1>Title</h1><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><div class=\"share-buttons\">Share</div></article><footer>Footer</footer></body>"
+        );
+        let content = extract(&parse(&html));
+
+        assert_eq!(content.name, "article");
+        assert!(content.find("h1").is_some());
+        assert!(!content.text().contains("Share"));
+    }
+
+    #[test]
+    fn test_extract_scored_content() {
+        let html = format!(
+            "<body><div id=\"menu\"><a href=\"/\">Home</a> <a href=\"/blog\">Blog</a></div><div class=\"wrapper\"><div class=\"post-body\"><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p></div><div class=\"cookie-banner\">We use cookies</div></div></body>"
+        );
+        let content = extract(&parse(&html));
+
+        assert_eq!(content.attr("class"), Some("post-body"));
+        assert!(!content.text().contains("Home"));
+    }
+
+    #[test]
+    fn test_extract_falls_back_to_body() {
+        let content = extract(&parse(
+            "<body><p>Short page.</p><script>x()</script></body>",
+        ));
+
+        assert_eq!(content.name, "body");
+        assert_eq!(content.text(), "Short page.");
+    }
+}
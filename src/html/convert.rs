@@ -0,0 +1,482 @@
+// Converts an HTML tree to Markdown. Blocks are rendered to strings bottom-up, so nested lists and
+// blockquotes are simply indented or prefixed versions of their rendered content.
+
+use super::dom::{Element, Node};
+
+// Elements that start a new block, anything else is rendered inline.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "#document",
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+// Elements that are never rendered.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "button", "head", "iframe", "input", "noscript", "script", "select", "style", "svg",
+    "template", "textarea", "title",
+];
+
+/// Renders the element's content as Markdown.
+pub(crate) fn to_markdown(element: &Element) -> String {
+    blocks(&element.children)
+}
+
+fn is_block(node: &Node) -> bool {
+    match node {
+        Node::Element(element) => BLOCK_ELEMENTS.contains(&element.name.as_str()),
+        Node::Text(_) => false,
+    }
+}
+
+// Renders a list of nodes, grouping consecutive inline nodes into paragraphs.
+fn blocks(nodes: &[Node]) -> String {
+    let mut rendered = Vec::new();
+    let mut start = 0;
+
+    while start < nodes.len() {
+        if let Node::Element(element) = &nodes[start]
+            && is_block(&nodes[start])
+        {
+            rendered.extend(block(element));
+            start += 1;
+            continue;
+        }
+
+        let end = nodes[start..]
+            .iter()
+            .position(is_block)
+            .map_or(nodes.len(), |offset| start + offset);
+        rendered.extend(paragraph(&nodes[start..end]));
+        start = end;
+    }
+
+    rendered.join("\n\n")
+}
+
+fn block(element: &Element) -> Option<String> {
+    let rendered = match element.name.as_str() {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = element.name[1..].parse::<usize>().unwrap_or(1);
+            let text = paragraph(&element.children)?.replace("  \n", " ");
+            format!("{} {}", "#".repeat(level), text)
+        }
+        "p" | "dt" | "figcaption" | "summary" => paragraph(&element.children)?,
+        "hr" => "---".to_string(),
+        "pre" => code_block(element),
+        "blockquote" => prefix_lines(&blocks(&element.children), "> ", ">"),
+        "ul" | "ol" => list(element)?,
+        "table" => table(element)?,
+        "dd" => prefix_lines(&blocks(&element.children), "    ", ""),
+        _ => blocks(&element.children),
+    };
+
+    (!rendered.trim().is_empty()).then_some(rendered)
+}
+
+fn paragraph(nodes: &[Node]) -> Option<String> {
+    let mut text = String::new();
+    for node in nodes {
+        inline(node, &mut text);
+    }
+
+    // Collapse the whitespace left between inline elements, and turn the line breaks of `<br>`
+    // into hard breaks.
+    let lines = text
+        .split('\n')
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .map(|line| escape_line_starts(&line))
+        .collect::<Vec<_>>();
+
+    (!lines.is_empty()).then(|| lines.join("  \n"))
+}
+
+fn inline(node: &Node, output: &mut String) {
+    let element = match node {
+        Node::Text(text) => {
+            output.push_str(&escape(&text.replace('\n', " ")));
+            return;
+        }
+        Node::Element(element) => element,
+    };
+
+    match element.name.as_str() {
+        name if SKIPPED_ELEMENTS.contains(&name) => (),
+        "br" => output.push('\n'),
+        "strong" | "b" => wrap(element, "**", output),
+        "em" | "i" | "cite" | "dfn" => wrap(element, "*", output),
+        "del" | "s" | "strike" => wrap(element, "~~", output),
+        "code" | "kbd" | "samp" | "tt" => {
+            let code = element.text().replace('\n', " ");
+            if !code.trim().is_empty() {
+                // The fence must be longer than any run of backticks in the code.
+                let longest = code
+                    .split(|c| c != '`')
+                    .map(str::len)
+                    .max()
+                    .unwrap_or_default();
+                let fence = "`".repeat(longest + 1);
+                let padding = if code.starts_with('`') || code.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+                output.push_str(&format!("{fence}{padding}{code}{padding}{fence}"));
+            }
+        }
+        "a" => {
+            let mut text = String::new();
+            for child in &element.children {
+                inline(child, &mut text);
+            }
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let href = element.attr("href").unwrap_or_default().trim();
+
+            if text.is_empty() {
+                return;
+            }
+            if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+                output.push_str(&text);
+                return;
+            }
+
+            output.push_str(&format!("[{}]({}", text, url(href)));
+            if let Some(title) = element.attr("title").filter(|title| !title.is_empty()) {
+                output.push_str(&format!(" \"{}\"", title.replace('"', "\\\"")));
+            }
+            output.push(')');
+        }
+        "img" => {
+            let src = element.attr("src").unwrap_or_default().trim();
+            if !src.is_empty() && !src.starts_with("data:") {
+                let alt = element.attr("alt").unwrap_or_default();
+                output.push_str(&format!("![{}]({})", escape(alt.trim()), url(src)));
+            }
+        }
+        _ => {
+            for child in &element.children {
+                inline(child, output);
+            }
+        }
+    }
+}
+
+fn wrap(element: &Element, marker: &str, output: &mut String) {
+    let mut text = String::new();
+    for child in &element.children {
+        inline(child, &mut text);
+    }
+
+    // Markers must hug the text, so surrounding whitespace is moved outside of them.
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        output.push_str(&text);
+        return;
+    }
+
+    if text.starts_with(char::is_whitespace) {
+        output.push(' ');
+    }
+    output.push_str(&format!("{marker}{trimmed}{marker}"));
+    if text.ends_with(char::is_whitespace) {
+        output.push(' ');
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes the characters at the start of each line that would turn it into a heading, list item or
+/// blockquote. This must be applied to whole lines of output, not to each piece of text in them.
+pub(crate) fn escape_line_starts(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        match block_marker(line) {
+            Some(marker) => {
+                escaped.push_str(&line[..marker]);
+                escaped.push('\\');
+                escaped.push_str(&line[marker..]);
+            }
+            None => escaped.push_str(line),
+        }
+    }
+    escaped
+}
+
+// Returns the position of the character that would turn the line into a heading, list item or
+// blockquote if it started a line of Markdown (e.g. `#`, `-` or the `.` of `1.`).
+fn block_marker(line: &str) -> Option<usize> {
+    let start = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let rest = &line[start..];
+    if rest.starts_with(['#', '-', '+', '>']) {
+        return Some(start);
+    }
+
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    (digits > 0 && rest[digits..].starts_with(['.', ')'])).then_some(start + digits)
+}
+
+// URLs with spaces or parentheses are wrapped in angle brackets so they stay a single destination.
+fn url(url: &str) -> String {
+    if url.contains([' ', '(', ')']) {
+        format!("<{}>", url.replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn code_block(element: &Element) -> String {
+    let code = element.text();
+    let code = code.strip_prefix('\n').unwrap_or(&code).trim_end();
+
+    let language = element
+        .find("code")
+        .into_iter()
+        .chain([element])
+        .filter_map(|element| element.attr("class"))
+        .flat_map(str::split_whitespace)
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or_default();
+
+    // The fence must be longer than any run of backticks in the code.
+    let mut fence = "```".to_string();
+    while code.contains(&fence) {
+        fence.push('`');
+    }
+
+    format!("{fence}{language}\n{code}\n{fence}")
+}
+
+fn list(element: &Element) -> Option<String> {
+    let ordered = element.name == "ol";
+    let mut number = element
+        .attr("start")
+        .and_then(|start| start.trim().parse::<u64>().ok())
+        .unwrap_or(1);
+
+    let mut items = Vec::new();
+    for child in &element.children {
+        let content = match child {
+            Node::Element(item) if item.name == "li" => blocks(&item.children),
+            // Lists nested directly in lists, without an item.
+            Node::Element(nested) if matches!(nested.name.as_str(), "ul" | "ol") => {
+                if let Some(nested) = list(nested) {
+                    items.push(prefix_lines(&nested, "    ", ""));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        let marker = if ordered {
+            format!("{}. ", number)
+        } else {
+            "- ".to_string()
+        };
+        number += 1;
+
+        let indent = " ".repeat(marker.len());
+        let content = prefix_lines(&content, &indent, "");
+        items.push(format!("{}{}", marker, content.trim_start()));
+    }
+
+    (!items.is_empty()).then(|| items.join("\n"))
+}
+
+fn table(element: &Element) -> Option<String> {
+    let mut rows = Vec::new();
+    collect_rows(element, &mut rows);
+    let columns = rows.iter().map(Vec::len).max()?;
+    if columns == 0 {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let mut cells = row.clone();
+        cells.resize(columns, String::new());
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+fn collect_rows(element: &Element, rows: &mut Vec<Vec<String>>) {
+    for child in element.elements() {
+        match child.name.as_str() {
+            "tr" => rows.push(
+                child
+                    .elements()
+                    .filter(|cell| matches!(cell.name.as_str(), "td" | "th"))
+                    .map(|cell| {
+                        paragraph(&cell.children)
+                            .unwrap_or_default()
+                            .replace("  \n", " ")
+                            .replace('|', "\\|")
+                    })
+                    .collect(),
+            ),
+            "thead" | "tbody" | "tfoot" => collect_rows(child, rows),
+            _ => (),
+        }
+    }
+}
+
+fn prefix_lines(text: &str, prefix: &str, blank_prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                blank_prefix.to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dom::parse;
+    use super::*;
+
+    fn convert(html: &str) -> String {
+        to_markdown(&parse(html))
+    }
+
+    #[test]
+    fn test_headings_and_paragraphs() {
+        assert_eq!(
+            convert(
+                "<h1>Title</h1><p>Some <b>bold</b>, <em>italic </em>and <code>code</code>.</p><h2>Next</h2>Loose <i>text</i><hr>"
+            ),
+            "# Title\n\nSome **bold**, *italic* and `code`.\n\n## Next\n\nLoose *text*\n\n---"
+        );
+    }
+
+    #[test]
+    fn test_links_and_images() {
+        assert_eq!(
+            convert(
+                "<p><a href=\"https://example.com\" title=\"Example\">a link</a> <a href=\"#top\">anchor</a> <a href=\"/x\"></a><img src=\"/logo.png\" alt=\"Logo\"><a href=\"/a b\">space</a></p>"
+            ),
+            "[a link](https://example.com \"Example\") anchor ![Logo](/logo.png)[space](</a b>)"
+        );
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            convert(
+                "<ul><li>One</li><li>Two<ul><li>Nested</li></ul></li></ul><ol start=\"3\"><li><p>Three</p><p>More</p></li><li>Four</li></ol>"
+            ),
+            "- One\n- Two\n\n  - Nested\n\n3. Three\n\n   More\n4. Four"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        assert_eq!(
+            convert(
+                "<pre><code class=\"language-rust\">fn main() {\n    println!(\"*hi*\");\n}\n</code></pre>"
+            ),
+            "```rust\nfn main() {\n    println!(\"*hi*\");\n}\n```"
+        );
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(
+            convert(
+                "<table><thead><tr><th>Name</th><th>Value</th></tr></thead><tbody><tr><td>a|b</td><td><b>1</b></td></tr><tr><td>c</td></tr></tbody></table>"
+            ),
+            "| Name | Value |\n| --- | --- |\n| a\\|b | **1** |\n| c |  |"
+        );
+    }
+
+    #[test]
+    fn test_blockquotes_and_breaks() {
+        assert_eq!(
+            convert("<blockquote><p>Quoted<br>line</p><p>Second</p></blockquote>"),
+            "> Quoted  \n> line\n>\n> Second"
+        );
+    }
+
+    #[test]
+    fn test_escapes_markdown() {
+        assert_eq!(
+            convert("<p>snake_case and *stars* [brackets] &lt;b&gt;</p>"),
+            "snake\\_case and \\*stars\\* \\[brackets\\] \\<b>"
+        );
+    }
+
+    #[test]
+    fn test_escapes_line_leading_markers() {
+        assert_eq!(
+            convert("<p># not a heading</p><p>- not<br>+ a<br>&gt; list<br>1. item</p>"),
+            "\\# not a heading\n\n\\- not  \n\\+ a  \n\\> list  \n1\\. item"
+        );
+        assert_eq!(
+            convert("<p>a <b>b</b> - c <i>d</i> 1. e</p>"),
+            "a **b** - c *d* 1. e"
+        );
+    }
+
+    #[test]
+    fn test_inline_code_fence() {
+        assert_eq!(
+            convert("<p><code>a ``` b</code> and <code>`tick</code></p>"),
+            "````a ``` b```` and `` `tick ``"
+        );
+    }
+}
@@ -0,0 +1,524 @@
+// A small, forgiving HTML parser that builds just enough of a tree to convert documents to
+// Markdown. It is not a conforming HTML5 parser, but it handles the common cases of real-world
+// markup: void and self-closing elements, unclosed paragraphs, list items and table cells, raw
+// text in scripts and styles, and character references.
+
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attrs: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+}
+
+impl Element {
+    fn new(name: &str, attrs: Vec<(String, String)>) -> Self {
+        Element {
+            name: name.to_string(),
+            attrs,
+            children: Vec::new(),
+        }
+    }
+
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Returns the first descendant (or the element itself) with the given name.
+    pub(crate) fn find(&self, name: &str) -> Option<&Element> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.elements().find_map(|child| child.find(name))
+    }
+
+    /// Returns all the descendants with the given name, in document order.
+    pub(crate) fn find_all(&self, name: &str) -> Vec<&Element> {
+        let mut found = Vec::new();
+        for child in self.elements() {
+            if child.name == name {
+                found.push(child);
+            }
+            found.extend(child.find_all(name));
+        }
+        found
+    }
+
+    /// Returns the text content of the element, with whitespace as it is in the document.
+    pub(crate) fn text(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text
+    }
+
+    fn collect_text(&self, text: &mut String) {
+        for child in &self.children {
+            match child {
+                Node::Text(content) => text.push_str(content),
+                Node::Element(element) => element.collect_text(text),
+            }
+        }
+    }
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+// Elements whose content is not parsed as HTML.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title", "xmp"];
+
+// Elements that implicitly close an open paragraph.
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+// How deeply elements can be nested, as the tree is walked recursively when converting it.
+const MAX_DEPTH: usize = 256;
+
+/// Parses the HTML into a tree, rooted at a `#document` element.
+pub(crate) fn parse(html: &str) -> Element {
+    let mut stack = vec![Element::new("#document", Vec::new())];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            // Doctypes, CDATA sections and processing instructions.
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let name = tag[..end]
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            close(&mut stack, &name);
+            rest = tag.get(end + 1..).unwrap_or("");
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let (name, attrs, self_closing, remaining) = parse_start_tag(&rest[1..]);
+            rest = remaining;
+            // Elements nested too deeply are dropped, but not their content.
+            let is_open = stack.len() <= MAX_DEPTH;
+            if is_open {
+                open(&mut stack, &name, attrs);
+            }
+
+            if VOID_ELEMENTS.contains(&name.as_str()) || self_closing {
+                if is_open {
+                    close(&mut stack, &name);
+                }
+            } else if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                let end = find_ignore_case(rest, &format!("</{}", name)).unwrap_or(rest.len());
+                let text = &rest[..end];
+                if is_open && !text.is_empty() {
+                    let text = match name.as_str() {
+                        "script" | "style" | "xmp" => text.to_string(),
+                        _ => decode_entities(text),
+                    };
+                    push_node(&mut stack, Node::Text(text));
+                }
+                if is_open {
+                    close(&mut stack, &name);
+                }
+                rest = &rest[end..];
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            }
+        } else {
+            // A stray `<` that doesn't start a tag.
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+        }
+    }
+
+    while stack.len() > 1 {
+        pop(&mut stack);
+    }
+    stack.pop().unwrap_or_default()
+}
+
+fn parse_start_tag(tag: &str) -> (String, Vec<(String, String)>, bool, &str) {
+    let name_end = tag
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(tag.len());
+    let name = tag[..name_end].to_ascii_lowercase();
+    let mut rest = &tag[name_end..];
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        if let Some(remaining) = rest.strip_prefix('>') {
+            rest = remaining;
+            break;
+        }
+        if let Some(remaining) = rest.strip_prefix('/') {
+            self_closing = remaining.starts_with('>');
+            rest = remaining;
+            continue;
+        }
+
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(remaining) = rest.strip_prefix('=') {
+            let remaining = remaining.trim_start();
+            let (raw, after) = match remaining.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &remaining[1..];
+                    match inner.find(quote) {
+                        Some(end) => (&inner[..end], &inner[end + 1..]),
+                        None => (inner, ""),
+                    }
+                }
+                _ => {
+                    let end = remaining
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(remaining.len());
+                    remaining.split_at(end)
+                }
+            };
+            value = decode_entities(raw);
+            rest = after;
+        }
+
+        if !attrs.iter().any(|(existing, _)| *existing == key) {
+            attrs.push((key, value));
+        }
+    }
+
+    (name, attrs, self_closing, rest)
+}
+
+fn open(stack: &mut Vec<Element>, name: &str, attrs: Vec<(String, String)>) {
+    // Close elements that can't contain the new one, as browsers do for unclosed tags.
+    let implicitly_closed: &[&str] = match name {
+        "li" => &["li"],
+        "dt" | "dd" => &["dt", "dd"],
+        "tr" => &["tr", "td", "th"],
+        "td" | "th" => &["td", "th"],
+        "thead" | "tbody" | "tfoot" => &["thead", "tbody", "tfoot", "tr", "td", "th"],
+        "option" => &["option"],
+        _ => &[],
+    };
+    // Boundaries past which the implicit closing doesn't look, e.g. a nested list.
+    let scope: &[&str] = match name {
+        "li" => &["ul", "ol"],
+        "dt" | "dd" => &["dl"],
+        "tr" | "td" | "th" | "thead" | "tbody" | "tfoot" => &["table"],
+        "option" => &["select", "datalist"],
+        _ => &[],
+    };
+
+    if CLOSES_PARAGRAPH.contains(&name) {
+        close_in_scope(stack, &["p"], &["button", "td", "th", "li", "table"]);
+    }
+    close_in_scope(stack, implicitly_closed, scope);
+
+    stack.push(Element::new(name, attrs));
+}
+
+// Closes the outermost open element with one of the names (and everything inside of it), without
+// looking past a scope boundary.
+fn close_in_scope(stack: &mut Vec<Element>, names: &[&str], scope: &[&str]) {
+    let mut outermost = None;
+    for i in (1..stack.len()).rev() {
+        let name = stack[i].name.as_str();
+        if names.contains(&name) {
+            outermost = Some(i);
+        } else if scope.contains(&name) {
+            break;
+        }
+    }
+
+    if let Some(i) = outermost {
+        while stack.len() > i {
+            pop(stack);
+        }
+    }
+}
+
+fn close(stack: &mut Vec<Element>, name: &str) {
+    // End tags without a matching open element are ignored.
+    if let Some(i) = stack.iter().rposition(|element| element.name == name)
+        && i > 0
+    {
+        while stack.len() > i {
+            pop(stack);
+        }
+    }
+}
+
+fn pop(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        push_node(stack, Node::Element(element));
+    }
+}
+
+fn push_node(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    let text = decode_entities(text);
+    if let Some(parent) = stack.last_mut() {
+        match parent.children.last_mut() {
+            Some(Node::Text(previous)) => previous.push_str(&text),
+            _ => parent.children.push(Node::Text(text)),
+        }
+    }
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", " "),
+    ("shy", ""),
+    ("zwj", ""),
+    ("zwnj", ""),
+    ("copy", "©"),
+    ("reg", "®"),
+    ("trade", "™"),
+    ("hellip", "…"),
+    ("mdash", "—"),
+    ("ndash", "–"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("sbquo", "‚"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("bdquo", "„"),
+    ("laquo", "«"),
+    ("raquo", "»"),
+    ("bull", "•"),
+    ("middot", "·"),
+    ("deg", "°"),
+    ("times", "×"),
+    ("divide", "÷"),
+    ("plusmn", "±"),
+    ("para", "¶"),
+    ("sect", "§"),
+    ("euro", "€"),
+    ("pound", "£"),
+    ("yen", "¥"),
+    ("cent", "¢"),
+    ("larr", "←"),
+    ("rarr", "→"),
+    ("uarr", "↑"),
+    ("darr", "↓"),
+];
+
+/// Replaces character references (e.g. `&amp;`, `&#39;` and `&#x27;`) with the characters they
+/// stand for, unknown references are left as-is.
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // References are short, so only the next few bytes are searched for the closing `;`.
+        let reference = rest.as_bytes()[1..rest.len().min(34)]
+            .iter()
+            .position(|byte| *byte == b';')
+            .map(|end| &rest[1..end + 1]);
+        let replacement = reference.and_then(|reference| {
+            if let Some(number) = reference.strip_prefix('#') {
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse().ok(),
+                };
+                code.map(|code| match code {
+                    0 => '\u{FFFD}',
+                    // Non-breaking spaces are plain spaces as far as Markdown is concerned.
+                    0xA0 => ' ',
+                    _ => char::from_u32(code).unwrap_or('\u{FFFD}'),
+                })
+                .map(String::from)
+            } else {
+                NAMED_ENTITIES
+                    .iter()
+                    .find(|(name, _)| *name == reference)
+                    .map(|(_, value)| value.to_string())
+            }
+        });
+
+        match (reference, replacement) {
+            (Some(reference), Some(replacement)) => {
+                decoded.push_str(&replacement);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(element: &Element) -> Vec<&str> {
+        element.elements().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_tree() {
+        let document = parse(
+            "<!DOCTYPE html><html><body><!-- comment --><p class=\"intro\" hidden>Hello <b>world</b><br/>again</p></body></html>",
+        );
+        let body = document.find("body").unwrap();
+        let p = body.find("p").unwrap();
+
+        assert_eq!(names(body), vec!["p"]);
+        assert_eq!(p.attr("class"), Some("intro"));
+        assert_eq!(p.attr("hidden"), Some(""));
+        assert_eq!(names(p), vec!["b", "br"]);
+        assert_eq!(p.text(), "Hello worldagain");
+    }
+
+    #[test]
+    fn test_implicit_closing() {
+        let document = parse(
+            "<p>One<p>Two<ul><li>A<li>B<ul><li>B1</ul></ul><table><tr><td>1<td>2<tr><td>3</table>",
+        );
+
+        assert_eq!(names(&document), vec!["p", "p", "ul", "table"]);
+        let list = document.find("ul").unwrap();
+        assert_eq!(names(list), vec!["li", "li"]);
+        assert_eq!(list.elements().nth(1).unwrap().text(), "BB1");
+
+        let table = document.find("table").unwrap();
+        assert_eq!(names(table), vec!["tr", "tr"]);
+        assert_eq!(names(table.elements().next().unwrap()), vec!["td", "td"]);
+    }
+
+    #[test]
+    fn test_raw_text() {
+        let document = parse("<script>if (a < b) { x = '</p>'; }</script><p>Text</p>");
+
+        assert_eq!(names(&document), vec!["script", "p"]);
+        assert_eq!(
+            document.find("script").unwrap().text(),
+            "if (a < b) { x = '</p>'; }"
+        );
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let html = "<div>".repeat(100_000) + "deep" + &"</div>".repeat(100_000);
+        let document = parse(&html);
+
+        let mut depth = 0;
+        let mut element = &document;
+        while let Some(child) = element.elements().next() {
+            depth += 1;
+            element = child;
+        }
+        assert_eq!(depth, MAX_DEPTH);
+        assert_eq!(document.text(), "deep");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities(
+                "Tom &amp; Jerry&#39;s &#x201C;show&#x201D;&nbsp;&hellip; &unknown; & more"
+            ),
+            "Tom & Jerry's “show” … &unknown; & more"
+        );
+        assert_eq!(decode_entities(&"&a".repeat(100_000)).len(), 200_000);
+        assert_eq!(
+            parse("<a href=\"/?a=1&amp;b=2\">x</a>")
+                .find("a")
+                .unwrap()
+                .attr("href"),
+            Some("/?a=1&b=2")
+        );
+    }
+}
@@ -0,0 +1,130 @@
+// Reads the metadata of a web page from its `<head>`: Open Graph and article meta tags first, then
+// the standard HTML elements (`<title>`, `<link rel="canonical">`, the `lang` attribute).
+
+use super::dom::Element;
+
+pub(crate) fn title(document: &Element) -> Option<String> {
+    meta(document, &["og:title", "twitter:title"])
+        .or_else(|| document.find("title").map(Element::text))
+        .or_else(|| document.find("h1").map(Element::text))
+        .and_then(normalize)
+}
+
+pub(crate) fn author(document: &Element) -> Option<String> {
+    meta(document, &["author", "article:author", "twitter:creator"]).and_then(normalize)
+}
+
+pub(crate) fn published_at(document: &Element) -> Option<String> {
+    meta(
+        document,
+        &["article:published_time", "date", "dc.date", "pubdate"],
+    )
+    .or_else(|| {
+        document
+            .find("time")
+            .and_then(|time| time.attr("datetime"))
+            .map(str::to_string)
+    })
+    .and_then(normalize)
+}
+
+pub(crate) fn canonical_url(document: &Element) -> Option<String> {
+    document
+        .find_all("link")
+        .into_iter()
+        .find(|link| has_rel(link, "canonical"))
+        .and_then(|link| link.attr("href"))
+        .map(str::to_string)
+        .or_else(|| meta(document, &["og:url"]))
+        .and_then(normalize)
+}
+
+pub(crate) fn language(document: &Element) -> Option<String> {
+    document
+        .find("html")
+        .and_then(|html| html.attr("lang"))
+        .map(str::to_string)
+        .or_else(|| meta(document, &["og:locale", "content-language"]))
+        .and_then(normalize)
+}
+
+pub(crate) fn lead_image_url(document: &Element) -> Option<String> {
+    meta(document, &["og:image", "og:image:url", "twitter:image"]).and_then(normalize)
+}
+
+// Returns the content of the first `<meta>` tag with one of the keys, in order of preference.
+fn meta(document: &Element, keys: &[&str]) -> Option<String> {
+    let tags = document.find_all("meta");
+    keys.iter().find_map(|key| {
+        tags.iter().find_map(|tag| {
+            let name = ["property", "name", "http-equiv"]
+                .into_iter()
+                .find_map(|attr| tag.attr(attr))?;
+            name.eq_ignore_ascii_case(key)
+                .then(|| tag.attr("content"))
+                .flatten()
+                .map(str::to_string)
+        })
+    })
+}
+
+fn has_rel(link: &Element, rel: &str) -> bool {
+    link.attr("rel").is_some_and(|value| {
+        value
+            .split_whitespace()
+            .any(|v| v.eq_ignore_ascii_case(rel))
+    })
+}
+
+fn normalize(text: String) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dom::parse;
+    use super::*;
+
+    #[test]
+    fn test_metadata() {
+        let document = parse(
+            r#"<html lang="en-US"><head>
+<title>Fallback | Blog</title>
+<meta property="og:title" content="The  real title">
+<meta name="author" content="Jane Doe">
+<meta property="article:published_time" content="2025-03-01T10:00:00Z">
+<link rel="canonical" href="https://example.com/post">
+<meta property="og:image" content="https://example.com/cover.png">
+</head><body><h1>Heading</h1></body></html>"#,
+        );
+
+        assert_eq!(title(&document).as_deref(), Some("The real title"));
+        assert_eq!(author(&document).as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            published_at(&document).as_deref(),
+            Some("2025-03-01T10:00:00Z")
+        );
+        assert_eq!(
+            canonical_url(&document).as_deref(),
+            Some("https://example.com/post")
+        );
+        assert_eq!(language(&document).as_deref(), Some("en-US"));
+        assert_eq!(
+            lead_image_url(&document).as_deref(),
+            Some("https://example.com/cover.png")
+        );
+    }
+
+    #[test]
+    fn test_metadata_fallbacks() {
+        let document = parse(
+            "<body><h1>Only a heading</h1><time datetime=\"2024-12-24\">Christmas eve</time></body>",
+        );
+
+        assert_eq!(title(&document).as_deref(), Some("Only a heading"));
+        assert_eq!(published_at(&document).as_deref(), Some("2024-12-24"));
+        assert_eq!(author(&document), None);
+        assert_eq!(canonical_url(&document), None);
+    }
+}
@@ -0,0 +1,84 @@
+mod content;
+mod convert;
+pub(crate) mod dom;
+mod metadata;
+
+pub(crate) use convert::{escape, escape_line_starts};
+
+use crate::types::{Article, HtmlToMarkdownOptions};
+
+/// Converts the HTML to Markdown without involving the host.
+pub(crate) fn to_markdown(html: &str, opts: &HtmlToMarkdownOptions) -> String {
+    let document = dom::parse(html);
+    let root = if opts.extract_main_content {
+        content::extract(&document)
+    } else {
+        content::strip(&document)
+    };
+
+    convert::to_markdown(&root)
+}
+
+/// Extracts the main content of the page as Markdown along with its metadata, without involving the
+/// host.
+pub(crate) fn extract_article(html: &str) -> Article {
+    let document = dom::parse(html);
+
+    Article {
+        title: metadata::title(&document),
+        author: metadata::author(&document),
+        published_at: metadata::published_at(&document),
+        canonical_url: metadata::canonical_url(&document),
+        language: metadata::language(&document),
+        lead_image_url: metadata::lead_image_url(&document),
+        markdown: convert::to_markdown(&content::extract(&document)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<head><title>Page</title><style>p { color: red; }</style></head>
+<body>
+  <nav><ul><li><a href="/">Home</a></li><li><a href="/blog">Blog</a></li></ul></nav>
+  <main>
+    <h1>Getting started</h1>
+    <p>Install the SDK with <code>cargo add hubble</code>, then register your plugin with the host.</p>
+    <pre><code class="language-sh">cargo build --target wasm32-wasip1</code></pre>
+  </main>
+  <footer>&copy; 2025 Example</footer>
+</body>
+</html>"#;
+
+        assert_eq!(
+            to_markdown(html, &HtmlToMarkdownOptions::default()),
+            "# Getting started\n\nInstall the SDK with `cargo add hubble`, then register your plugin with the host.\n\n```sh\ncargo build --target wasm32-wasip1\n```"
+        );
+
+        let opts = HtmlToMarkdownOptions {
+            extract_main_content: false,
+        };
+        let markdown = to_markdown(html, &opts);
+        assert!(markdown.starts_with("- [Home](/)\n- [Blog](/blog)\n\n# Getting started"));
+        assert!(markdown.ends_with("© 2025 Example"));
+    }
+
+    #[test]
+    fn test_extract_article() {
+        let article = extract_article(
+            r#"<html lang="de"><head><title>Erste Schritte</title><meta name="author" content="Max"></head>
+<body><nav><a href="/">Start</a></nav><article><h1>Erste Schritte</h1><p>Hallo.</p></article></body></html>"#,
+        );
+
+        assert_eq!(article.title.as_deref(), Some("Erste Schritte"));
+        assert_eq!(article.author.as_deref(), Some("Max"));
+        assert_eq!(article.language.as_deref(), Some("de"));
+        assert_eq!(article.canonical_url, None);
+        assert_eq!(article.markdown, "# Erste Schritte\n\nHallo.");
+    }
+}
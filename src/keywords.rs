@@ -0,0 +1,166 @@
+// Keyword extraction with RAKE (Rapid Automatic Keyword Extraction): the text is split into
+// candidate phrases at stopwords and punctuation, each word is scored by how many words it
+// co-occurs with relative to how often it appears, and a phrase scores the sum of its words.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    language::{self, Language},
+    stopwords::stopwords,
+    types::Keyword,
+};
+
+// Longer candidates are usually fragments of sentences rather than keywords.
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Returns the `n` highest scoring keywords (or key phrases) of the text, best first.
+pub(crate) fn keywords(text: &str, n: usize) -> Vec<Keyword> {
+    let stopwords = stopword_set(text);
+
+    let mut phrases = Vec::new();
+    for clause in text.split(is_phrase_boundary) {
+        let mut phrase = Vec::new();
+        for word in words(clause) {
+            let is_candidate = word.chars().count() > 1
+                && !word.chars().all(|c| c.is_numeric())
+                && !stopwords.contains(word.as_str());
+
+            if is_candidate {
+                phrase.push(word);
+            } else if !phrase.is_empty() {
+                phrases.push(std::mem::take(&mut phrase));
+            }
+        }
+        if !phrase.is_empty() {
+            phrases.push(phrase);
+        }
+    }
+    phrases.retain(|phrase| phrase.len() <= MAX_PHRASE_WORDS);
+
+    let mut frequency = HashMap::<&str, f64>::new();
+    let mut degree = HashMap::<&str, f64>::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *frequency.entry(word).or_default() += 1.0;
+            *degree.entry(word).or_default() += phrase.len() as f64;
+        }
+    }
+
+    // Phrases are ranked once, in the order they first appear so ties are stable.
+    let mut seen = HashSet::new();
+    let mut keywords = phrases
+        .iter()
+        .filter(|phrase| seen.insert(phrase.join(" ")))
+        .map(|phrase| Keyword {
+            text: phrase.join(" "),
+            score: phrase
+                .iter()
+                .map(|word| degree[word.as_str()] / frequency[word.as_str()])
+                .sum(),
+        })
+        .collect::<Vec<_>>();
+
+    keywords.sort_by(|a, b| b.score.total_cmp(&a.score));
+    keywords.truncate(n);
+    keywords
+}
+
+/// Returns the stopwords of the language the text is written in.
+///
+/// Text too short or ambiguous to detect is assumed to be English.
+pub(crate) fn stopword_set(text: &str) -> HashSet<&'static str> {
+    let language = match language::detect_lang(text) {
+        Language::Simple => Language::English,
+        language => language,
+    };
+    stopwords(language).iter().copied().collect()
+}
+
+/// Splits the text into lowercase words.
+pub(crate) fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’' && c != '-')
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn is_phrase_boundary(c: char) -> bool {
+    matches!(
+        c,
+        '.' | ','
+            | ';'
+            | ':'
+            | '!'
+            | '?'
+            | '('
+            | ')'
+            | '['
+            | ']'
+            | '{'
+            | '}'
+            | '"'
+            | '“'
+            | '”'
+            | '«'
+            | '»'
+            | '/'
+            | '|'
+            | '\n'
+            | '–'
+            | '—'
+            | '。'
+            | '，'
+            | '、'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keywords() {
+        let text = "Compatibility of systems of linear constraints over the set of natural numbers. \
+            Criteria of compatibility of a system of linear Diophantine equations, strict inequations, \
+            and nonstrict inequations are considered. Upper bounds for components of a minimal set of \
+            solutions and algorithms of construction of minimal generating sets of solutions for all \
+            types of systems are given.";
+
+        let keywords = keywords(text, 4)
+            .into_iter()
+            .map(|keyword| keyword.text)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keywords,
+            vec![
+                "linear diophantine equations",
+                "minimal generating sets",
+                "linear constraints",
+                "natural numbers"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keywords_without_candidates() {
+        assert!(keywords("", 5).is_empty());
+        assert!(keywords("It is what it is.", 5).is_empty());
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            words("Don't split state-of-the-art, (but do) split 'quotes'.").collect::<Vec<_>>(),
+            vec![
+                "don't",
+                "split",
+                "state-of-the-art",
+                "but",
+                "do",
+                "split",
+                "quotes"
+            ]
+        );
+    }
+}
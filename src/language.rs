@@ -1,6 +1,8 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug, Clone, Copy)]
+use crate::{chunker, error::Error, types::Chunk};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// This represents all languages supported by Postgres.
 pub enum Language {
     Simple,
@@ -34,53 +36,369 @@ pub enum Language {
     Yiddish,
 }
 
+// The Postgres text search configuration and the ISO 639-1 and 639-3 codes of each language.
+const LANGUAGES: &[(Language, &str, &str, &str)] = &[
+    (Language::Arabic, "arabic", "ar", "ara"),
+    (Language::Armenian, "armenian", "hy", "hye"),
+    (Language::Basque, "basque", "eu", "eus"),
+    (Language::Catalan, "catalan", "ca", "cat"),
+    (Language::Danish, "danish", "da", "dan"),
+    (Language::Dutch, "dutch", "nl", "nld"),
+    (Language::English, "english", "en", "eng"),
+    (Language::Finnish, "finnish", "fi", "fin"),
+    (Language::French, "french", "fr", "fra"),
+    (Language::German, "german", "de", "deu"),
+    (Language::Greek, "greek", "el", "ell"),
+    (Language::Hindi, "hindi", "hi", "hin"),
+    (Language::Hungarian, "hungarian", "hu", "hun"),
+    (Language::Indonesian, "indonesian", "id", "ind"),
+    (Language::Irish, "irish", "ga", "gle"),
+    (Language::Italian, "italian", "it", "ita"),
+    (Language::Lithuanian, "lithuanian", "lt", "lit"),
+    (Language::Nepali, "nepali", "ne", "nep"),
+    (Language::Norwegian, "norwegian", "no", "nor"),
+    (Language::Portuguese, "portuguese", "pt", "por"),
+    (Language::Romanian, "romanian", "ro", "ron"),
+    (Language::Russian, "russian", "ru", "rus"),
+    (Language::Serbian, "serbian", "sr", "srp"),
+    (Language::Spanish, "spanish", "es", "spa"),
+    (Language::Swedish, "swedish", "sv", "swe"),
+    (Language::Tamil, "tamil", "ta", "tam"),
+    (Language::Turkish, "turkish", "tr", "tur"),
+    (Language::Yiddish, "yiddish", "yi", "yid"),
+];
+
+// Codes that aren't the canonical ones above but name the same language, e.g. Norwegian Bokmål
+// and Nynorsk, or the bibliographic ISO 639-2 codes.
+const ALIASES: &[(&str, Language)] = &[
+    ("nb", Language::Norwegian),
+    ("nn", Language::Norwegian),
+    ("nob", Language::Norwegian),
+    ("nno", Language::Norwegian),
+    ("baq", Language::Basque),
+    ("dut", Language::Dutch),
+    ("fre", Language::French),
+    ("ger", Language::German),
+    ("gre", Language::Greek),
+    ("arm", Language::Armenian),
+    ("rum", Language::Romanian),
+];
+
+impl Language {
+    /// Returns the name of the Postgres text search configuration for the language, e.g.
+    /// `english`, as used in `to_tsvector('english', ...)`.
+    pub fn as_postgres_config(&self) -> &'static str {
+        self.entry().map_or("simple", |(_, config, _, _)| config)
+    }
+
+    /// Returns the ISO 639-1 code of the language, e.g. `en`, or `None` for `Language::Simple`.
+    pub fn iso_639_1(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, code, _)| code)
+    }
+
+    /// Returns the ISO 639-3 code of the language, e.g. `eng`, or `None` for `Language::Simple`.
+    pub fn iso_639_3(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, _, code)| code)
+    }
+
+    /// Returns the language with the given ISO 639-1 code (e.g. `en`), ignoring case and region
+    /// subtags (`en-US`).
+    pub fn from_iso_639_1(code: &str) -> Option<Self> {
+        let code = primary_subtag(code);
+        LANGUAGES
+            .iter()
+            .find(|(_, _, iso, _)| *iso == code)
+            .map(|(language, _, _, _)| *language)
+            .or_else(|| alias(&code).filter(|_| code.len() == 2))
+    }
+
+    /// Returns the language with the given ISO 639-3 code (e.g. `eng`), ignoring case.
+    pub fn from_iso_639_3(code: &str) -> Option<Self> {
+        let code = primary_subtag(code);
+        LANGUAGES
+            .iter()
+            .find(|(_, _, _, iso)| *iso == code)
+            .map(|(language, _, _, _)| *language)
+            .or_else(|| alias(&code).filter(|_| code.len() == 3))
+    }
+
+    fn entry(&self) -> Option<(Language, &'static str, &'static str, &'static str)> {
+        LANGUAGES
+            .iter()
+            .find(|(language, _, _, _)| language == self)
+            .copied()
+    }
+}
+
+fn primary_subtag(code: &str) -> String {
+    code.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn alias(code: &str) -> Option<Language> {
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == code)
+        .map(|(_, language)| *language)
+}
+
+/// Formats the language as its Postgres text search configuration, see
+/// [`Language::as_postgres_config`].
 impl Display for Language {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let lang_str = match self {
-            Language::Simple => "Simple",
-            Language::Arabic => "Arabic",
-            Language::Armenian => "Armenian",
-            Language::Basque => "Basque",
-            Language::Catalan => "Catalan",
-            Language::Danish => "danish",
-            Language::Dutch => "dutch",
-            Language::English => "english",
-            Language::Finnish => "finnish",
-            Language::French => "french",
-            Language::German => "german",
-            Language::Greek => "greek",
-            Language::Hindi => "hindi",
-            Language::Hungarian => "hungarian",
-            Language::Indonesian => "indonesian",
-            Language::Irish => "irish",
-            Language::Italian => "italian",
-            Language::Lithuanian => "lithuanian",
-            Language::Nepali => "nepali",
-            Language::Norwegian => "norwegian",
-            Language::Portuguese => "portuguese",
-            Language::Romanian => "romanian",
-            Language::Russian => "russian",
-            Language::Serbian => "serbian",
-            Language::Spanish => "spanish",
-            Language::Swedish => "swedish",
-            Language::Tamil => "tamil",
-            Language::Turkish => "turkish",
-            Language::Yiddish => "yiddish",
-        };
+        f.write_str(self.as_postgres_config())
+    }
+}
+
+/// Parses a Postgres text search configuration (`english`), as stored in `EntryChunk.language`,
+/// or an ISO 639-1 or 639-3 code (`en`, `eng`). Case is ignored, so values written by older
+/// versions (`Simple`, `Arabic`) are read back as well.
+impl FromStr for Language {
+    type Err = Error;
 
-        f.write_str(lang_str)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        if name == "simple" {
+            return Ok(Language::Simple);
+        }
+
+        LANGUAGES
+            .iter()
+            .find(|(_, config, _, _)| *config == name)
+            .map(|(language, _, _, _)| *language)
+            .or_else(|| Language::from_iso_639_1(&name))
+            .or_else(|| Language::from_iso_639_3(&name))
+            .ok_or_else(|| Error::InvalidArguments(format!("Unknown language: {}", s)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_postgres_config())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
     }
 }
 
 /// This is used to infer the Postgres-compatible language from a given text.
+///
+/// Dutch and Norwegian (Bokmål) texts are detected as [`Language::Dutch`] and
+/// [`Language::Norwegian`]. Text in a language Postgres has no configuration for, or that is too
+/// short to tell, is [`Language::Simple`].
 pub fn detect_lang(text: &str) -> Language {
-    let lang = whatlang::detect_lang(text);
-    if lang.is_none() {
-        return Language::Simple;
+    whatlang::detect_lang(text).map_or(Language::Simple, from_whatlang)
+}
+
+/// Whether the text is mostly written in the Latin, Cyrillic or Greek alphabet.
+pub(crate) fn is_european_alphabet(text: &str) -> bool {
+    matches!(
+        whatlang::detect_script(text),
+        Some(whatlang::Script::Latin | whatlang::Script::Cyrillic | whatlang::Script::Greek)
+    )
+}
+
+/// The language of a text as detected by [`detect_lang_info`].
+#[derive(Debug, Clone)]
+pub struct LanguageInfo {
+    /// The Postgres language of the text. This is `Language::Simple` if no language was detected
+    /// with enough confidence, or if the detected language has no Postgres configuration.
+    pub language: Language,
+    /// The ISO 639-1 code of the detected language, e.g. `en`.
+    pub iso_639_1: Option<&'static str>,
+    /// The ISO 639-3 code of the detected language, e.g. `eng`.
+    pub iso_639_3: Option<&'static str>,
+    /// The writing system of the text, e.g. `Latin` or `Cyrillic`.
+    pub script: Option<String>,
+    /// How confident the detection is, from 0 to 1.
+    pub confidence: f64,
+    /// Whether the detection can be trusted. Short texts and texts mixing languages are often not
+    /// reliable.
+    pub is_reliable: bool,
+}
+
+/// Options for [`detect_lang_info`]. The defaults accept any detection, in any language.
+#[derive(Debug, Clone, Default)]
+pub struct DetectOptions {
+    /// Detections with a lower confidence (from 0 to 1) are reported as `Language::Simple`.
+    pub min_confidence: f64,
+    /// The languages the text may be in, or all languages if empty. `Language::Simple` and the
+    /// languages that can't be detected (Basque, Irish) are ignored.
+    pub allowlist: Vec<Language>,
+}
+
+/// Detects the language of the text along with its ISO codes, script and how confident the
+/// detection is.
+///
+/// Unlike [`detect_lang`], a detection below `opts.min_confidence` falls back to
+/// `Language::Simple`. The ISO codes, script and confidence still describe the best guess, so
+/// callers can decide for themselves.
+pub fn detect_lang_info(text: &str, opts: &DetectOptions) -> LanguageInfo {
+    let allowlist = opts
+        .allowlist
+        .iter()
+        .filter_map(|language| to_whatlang(*language))
+        .collect::<Vec<_>>();
+    let detector = if allowlist.is_empty() {
+        whatlang::Detector::new()
+    } else {
+        whatlang::Detector::with_allowlist(allowlist)
+    };
+
+    let Some(info) = detector.detect(text) else {
+        return LanguageInfo {
+            language: Language::Simple,
+            iso_639_1: None,
+            iso_639_3: None,
+            script: None,
+            confidence: 0.0,
+            is_reliable: false,
+        };
+    };
+
+    let language = if info.confidence() >= opts.min_confidence {
+        from_whatlang(info.lang())
+    } else {
+        Language::Simple
+    };
+
+    LanguageInfo {
+        language,
+        iso_639_1: iso_639_1(info.lang()),
+        iso_639_3: Some(info.lang().code()),
+        script: Some(info.script().name().to_string()),
+        confidence: info.confidence(),
+        is_reliable: info.is_reliable(),
+    }
+}
+
+/// A part of a text written in a single language, as returned by [`segment_by_language`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageSpan {
+    pub language: Language,
+    /// Byte offset of the start of the span in the text.
+    pub start: usize,
+    /// Byte offset of the end (exclusive) of the span in the text.
+    pub end: usize,
+}
+
+// Sentences shorter than this, or detected with a lower confidence, take the language around them.
+// whatlang's own reliability flag is too strict for single sentences.
+const MIN_SEGMENT_CHARS: usize = 20;
+const MIN_SEGMENT_CONFIDENCE: f64 = 0.5;
+
+/// Splits the text into spans of consecutive sentences in the same language, e.g. an English
+/// abstract followed by a German body.
+///
+/// Sentences are detected one by one, and sentences too short or ambiguous to be detected reliably
+/// take the language of the sentences before them (or after them, at the start of the text).
+/// Spans cover the whole text, without gaps.
+pub fn segment_by_language(text: &str) -> Vec<LanguageSpan> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let sentences = chunker::sentence_spans(text);
+
+    let mut languages = sentences
+        .iter()
+        .map(|sentence| {
+            let sentence = &text[sentence.clone()];
+            let info = detect_lang_info(sentence, &DetectOptions::default());
+            let is_reliable = (info.is_reliable || info.confidence >= MIN_SEGMENT_CONFIDENCE)
+                && sentence.chars().count() >= MIN_SEGMENT_CHARS;
+            is_reliable.then_some(info.language)
+        })
+        .collect::<Vec<_>>();
+
+    // Fill in the gaps from the previous sentence, then from the next one for the leading ones.
+    for i in 1..languages.len() {
+        if languages[i].is_none() {
+            languages[i] = languages[i - 1];
+        }
+    }
+    for i in (0..languages.len().saturating_sub(1)).rev() {
+        if languages[i].is_none() {
+            languages[i] = languages[i + 1];
+        }
+    }
+
+    // Either every sentence has a language now, or none of them could be detected reliably.
+    let fallback = match languages.first() {
+        Some(None) => detect_lang(text),
+        _ => Language::Simple,
+    };
+
+    let mut spans: Vec<LanguageSpan> = Vec::new();
+    for (sentence, language) in sentences.iter().zip(languages) {
+        let language = language.unwrap_or(fallback);
+        match spans.last_mut() {
+            Some(span) if span.language == language => (),
+            Some(span) => {
+                span.end = sentence.start;
+                spans.push(LanguageSpan {
+                    language,
+                    start: sentence.start,
+                    end: sentence.start,
+                });
+            }
+            None => spans.push(LanguageSpan {
+                language,
+                start: 0,
+                end: 0,
+            }),
+        }
+    }
+
+    if let Some(span) = spans.last_mut() {
+        span.end = text.len();
     }
+    spans
+}
+
+/// Tags each chunk with the language most of its text is in, according to the language spans of
+/// the text the chunks were taken from.
+///
+/// Chunks without a range in the text (e.g. altered by the host) are detected on their own.
+pub(crate) fn tag_chunks(text: &str, chunks: &mut [Chunk]) {
+    let spans = segment_by_language(text);
+
+    for chunk in chunks {
+        let mut overlaps: Vec<(Language, usize)> = Vec::new();
+        for span in &spans {
+            let overlap = chunk
+                .end
+                .min(span.end)
+                .saturating_sub(chunk.start.max(span.start));
+            if overlap == 0 {
+                continue;
+            }
+            match overlaps
+                .iter_mut()
+                .find(|(language, _)| *language == span.language)
+            {
+                Some((_, total)) => *total += overlap,
+                None => overlaps.push((span.language, overlap)),
+            }
+        }
 
-    let lang = lang.unwrap();
+        chunk.language = match overlaps.into_iter().max_by_key(|(_, total)| *total) {
+            Some((language, _)) => language,
+            None => detect_lang(&chunk.text),
+        };
+    }
+}
 
+fn from_whatlang(lang: whatlang::Lang) -> Language {
     match lang {
         whatlang::Lang::Eng => Language::English,
         whatlang::Lang::Rus => Language::Russian,
@@ -106,6 +424,242 @@ pub fn detect_lang(text: &str) -> Language {
         whatlang::Lang::Nep => Language::Nepali,
         whatlang::Lang::Cat => Language::Catalan,
         whatlang::Lang::Hye => Language::Armenian,
+        whatlang::Lang::Nld => Language::Dutch,
+        whatlang::Lang::Nob => Language::Norwegian,
         _ => Language::Simple,
     }
 }
+
+fn to_whatlang(language: Language) -> Option<whatlang::Lang> {
+    let lang = match language {
+        Language::Arabic => whatlang::Lang::Ara,
+        Language::Armenian => whatlang::Lang::Hye,
+        Language::Catalan => whatlang::Lang::Cat,
+        Language::Danish => whatlang::Lang::Dan,
+        Language::Dutch => whatlang::Lang::Nld,
+        Language::English => whatlang::Lang::Eng,
+        Language::Finnish => whatlang::Lang::Fin,
+        Language::French => whatlang::Lang::Fra,
+        Language::German => whatlang::Lang::Deu,
+        Language::Greek => whatlang::Lang::Ell,
+        Language::Hindi => whatlang::Lang::Hin,
+        Language::Hungarian => whatlang::Lang::Hun,
+        Language::Indonesian => whatlang::Lang::Ind,
+        Language::Italian => whatlang::Lang::Ita,
+        Language::Lithuanian => whatlang::Lang::Lit,
+        Language::Nepali => whatlang::Lang::Nep,
+        Language::Norwegian => whatlang::Lang::Nob,
+        Language::Portuguese => whatlang::Lang::Por,
+        Language::Romanian => whatlang::Lang::Ron,
+        Language::Russian => whatlang::Lang::Rus,
+        Language::Serbian => whatlang::Lang::Srp,
+        Language::Spanish => whatlang::Lang::Spa,
+        Language::Swedish => whatlang::Lang::Swe,
+        Language::Tamil => whatlang::Lang::Tam,
+        Language::Turkish => whatlang::Lang::Tur,
+        Language::Yiddish => whatlang::Lang::Yid,
+        Language::Simple | Language::Basque | Language::Irish => return None,
+    };
+    Some(lang)
+}
+
+// The ISO 639-1 codes of the languages whatlang detects, by their ISO 639-3 code.
+const ISO_639_1: &[(&str, &str)] = &[
+    ("afr", "af"),
+    ("aka", "ak"),
+    ("amh", "am"),
+    ("ara", "ar"),
+    ("aze", "az"),
+    ("bel", "be"),
+    ("ben", "bn"),
+    ("bul", "bg"),
+    ("cat", "ca"),
+    ("ces", "cs"),
+    ("cmn", "zh"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ell", "el"),
+    ("eng", "en"),
+    ("epo", "eo"),
+    ("est", "et"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("guj", "gu"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hrv", "hr"),
+    ("hun", "hu"),
+    ("hye", "hy"),
+    ("ind", "id"),
+    ("ita", "it"),
+    ("jav", "jv"),
+    ("jpn", "ja"),
+    ("kan", "kn"),
+    ("kat", "ka"),
+    ("khm", "km"),
+    ("kor", "ko"),
+    ("lat", "la"),
+    ("lav", "lv"),
+    ("lit", "lt"),
+    ("mal", "ml"),
+    ("mar", "mr"),
+    ("mkd", "mk"),
+    ("mya", "my"),
+    ("nep", "ne"),
+    ("nld", "nl"),
+    ("nob", "nb"),
+    ("ori", "or"),
+    ("pan", "pa"),
+    ("pes", "fa"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rus", "ru"),
+    ("sin", "si"),
+    ("slk", "sk"),
+    ("slv", "sl"),
+    ("sna", "sn"),
+    ("spa", "es"),
+    ("srp", "sr"),
+    ("swe", "sv"),
+    ("tam", "ta"),
+    ("tel", "te"),
+    ("tgl", "tl"),
+    ("tha", "th"),
+    ("tuk", "tk"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("urd", "ur"),
+    ("uzb", "uz"),
+    ("vie", "vi"),
+    ("yid", "yi"),
+    ("zul", "zu"),
+];
+
+fn iso_639_1(lang: whatlang::Lang) -> Option<&'static str> {
+    ISO_639_1
+        .iter()
+        .find(|(code, _)| *code == lang.code())
+        .map(|(_, code)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GERMAN: &str =
+        "Die Katze sitzt auf der Matte und schaut aus dem Fenster, während es draußen regnet.";
+
+    const ENGLISH: &str =
+        "This paper studies how plugins can process documents without leaving the sandbox.";
+
+    #[test]
+    fn test_segment_by_language() {
+        let text = format!("{ENGLISH} Short one.\n\n{GERMAN} Ja.");
+        let spans = segment_by_language(&text);
+
+        assert_eq!(
+            spans,
+            vec![
+                LanguageSpan {
+                    language: Language::English,
+                    start: 0,
+                    end: ENGLISH.len() + " Short one.\n\n".len(),
+                },
+                LanguageSpan {
+                    language: Language::German,
+                    start: ENGLISH.len() + " Short one.\n\n".len(),
+                    end: text.len(),
+                },
+            ]
+        );
+        assert!(segment_by_language(" ").is_empty());
+    }
+
+    #[test]
+    fn test_tag_chunks() {
+        let text = format!("{ENGLISH}\n\n{GERMAN}");
+        let mut chunks = chunker::by_paragraph(&text);
+        for chunk in chunks.iter_mut() {
+            chunk.language = Language::Simple;
+        }
+        tag_chunks(&text, &mut chunks);
+
+        let languages = chunks
+            .iter()
+            .map(|chunk| chunk.language)
+            .collect::<Vec<_>>();
+        assert_eq!(languages, vec![Language::English, Language::German]);
+    }
+
+    #[test]
+    fn test_language_round_trip() {
+        for (language, _, _, _) in LANGUAGES {
+            assert_eq!(language.to_string().parse::<Language>().unwrap(), *language);
+        }
+        assert_eq!(Language::Simple.to_string(), "simple");
+        assert_eq!("simple".parse::<Language>().unwrap(), Language::Simple);
+    }
+
+    #[test]
+    fn test_parse_language() {
+        // Names written by older versions were capitalised.
+        assert_eq!("Arabic".parse::<Language>().unwrap(), Language::Arabic);
+        assert_eq!("Simple".parse::<Language>().unwrap(), Language::Simple);
+        assert_eq!(" ENGLISH ".parse::<Language>().unwrap(), Language::English);
+        assert_eq!("de".parse::<Language>().unwrap(), Language::German);
+        assert_eq!("fra".parse::<Language>().unwrap(), Language::French);
+        assert!("klingon".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_iso_codes() {
+        assert_eq!(Language::English.iso_639_1(), Some("en"));
+        assert_eq!(Language::English.iso_639_3(), Some("eng"));
+        assert_eq!(Language::Simple.iso_639_1(), None);
+        assert_eq!(
+            Language::from_iso_639_1("pt-BR"),
+            Some(Language::Portuguese)
+        );
+        assert_eq!(Language::from_iso_639_1("nb"), Some(Language::Norwegian));
+        assert_eq!(Language::from_iso_639_1("nob"), None);
+        assert_eq!(Language::from_iso_639_3("GLE"), Some(Language::Irish));
+        assert_eq!(Language::from_iso_639_3("zho"), None);
+    }
+
+    #[test]
+    fn test_detect_lang_info() {
+        let info = detect_lang_info(GERMAN, &DetectOptions::default());
+
+        assert!(info.language == Language::German);
+        assert_eq!(info.iso_639_1, Some("de"));
+        assert_eq!(info.iso_639_3, Some("deu"));
+        assert_eq!(info.script.as_deref(), Some("Latin"));
+        assert!(info.confidence > 0.5);
+    }
+
+    #[test]
+    fn test_detect_lang_info_options() {
+        let strict = DetectOptions {
+            min_confidence: 1.1,
+            ..Default::default()
+        };
+        let info = detect_lang_info(GERMAN, &strict);
+        assert_eq!(info.language, Language::Simple);
+        assert_eq!(info.iso_639_1, Some("de"));
+
+        let allowlist = DetectOptions {
+            allowlist: vec![Language::English, Language::French],
+            ..Default::default()
+        };
+        let info = detect_lang_info(GERMAN, &allowlist);
+        assert!(matches!(
+            info.language,
+            Language::English | Language::French
+        ));
+
+        let info = detect_lang_info("", &DetectOptions::default());
+        assert_eq!(info.language, Language::Simple);
+        assert!(!info.is_reliable);
+    }
+}
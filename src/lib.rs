@@ -1,17 +1,34 @@
 pub mod allocator;
+pub(crate) mod chunker;
+pub(crate) mod cleanup;
 pub mod crypto;
+pub(crate) mod document;
 pub mod entry;
 pub mod error;
+pub(crate) mod front_matter;
 pub(crate) mod host;
+pub(crate) mod html;
+pub(crate) mod keywords;
 pub mod language;
 pub mod log;
 pub mod macros;
 pub(crate) mod markdown;
 pub mod network;
+pub(crate) mod outline;
+pub(crate) mod render;
+pub(crate) mod stats;
+pub(crate) mod stopwords;
 pub mod store;
+pub(crate) mod summary;
+pub mod time;
+pub mod tokenizer;
 pub mod transform;
 pub mod types;
 
+#[cfg(feature = "getrandom")]
+#[doc(hidden)]
+pub use getrandom;
+
 pub(crate) mod entry_capnp {
     include!(concat!(env!("OUT_DIR"), "/entry_capnp.rs"));
 }
@@ -25,14 +42,10 @@ pub(crate) mod network_capnp {
 }
 
 /// Generates a SHA-256 checksum for the given data.
+///
+/// See [`crypto::hash`] for other algorithms, incremental hashing and other output encodings.
 pub fn generate_checksum(data: &[u8]) -> String {
-    use sha2::{Digest, Sha256};
-
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    let result = hasher.finalize();
-
-    format!("{:x}", result)
+    crypto::hash::digest(crypto::hash::Algorithm::Sha256, data).to_hex()
 }
 
 #[cfg(test)]
@@ -95,3 +95,34 @@ macro_rules! capnp_message_to_type {
         Ok(result)
     }};
 }
+
+/// Registers the host entropy source as the custom backend for `getrandom`, so crates like `rand`
+/// and `uuid` work inside plugins.
+///
+/// This must be invoked exactly once, in the plugin's root crate, and `getrandom` has to be told to
+/// use the custom backend, e.g. in `.cargo/config.toml`:
+///
+/// ```toml
+/// [target.wasm32-unknown-unknown]
+/// rustflags = ['--cfg', 'getrandom_backend="custom"']
+/// ```
+#[cfg(feature = "getrandom")]
+#[macro_export]
+macro_rules! register_getrandom_backend {
+    () => {
+        #[unsafe(no_mangle)]
+        unsafe extern "Rust" fn __getrandom_v03_custom(
+            dest: *mut u8,
+            len: usize,
+        ) -> Result<(), $crate::getrandom::Error> {
+            // SAFETY: getrandom guarantees `dest` is valid for `len` bytes, it may be
+            // uninitialized so it is zeroed before creating a slice over it.
+            let buf = unsafe {
+                core::ptr::write_bytes(dest, 0, len);
+                core::slice::from_raw_parts_mut(dest, len)
+            };
+
+            $crate::crypto::fill_bytes(buf).map_err(|_| $crate::getrandom::Error::new_custom(0))
+        }
+    };
+}
@@ -1,13 +1,36 @@
 // This file was vendored from https://github.com/fbecart/markdown_to_text
-use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{BrokenLink, CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+
+use crate::types::{LinkStyle, PlainTextOptions};
+
+const TABLE_CELL_SEPARATOR: &str = " | ";
+
+const BARE_URL_PREFIXES: &[&str] = &["http://", "https://", "www."];
 
 pub fn to_plain_text(markdown: &str) -> String {
-    // GFM tables and tasks lists are not enabled.
+    to_plain_text_with(markdown, &PlainTextOptions::default())
+}
+
+pub fn to_plain_text_with(markdown: &str, opts: &PlainTextOptions) -> String {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_STRIKETHROUGH);
-
-    let parser = Parser::new_ext(markdown, options);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+
+    // `[https://example.com]` is not a link in CommonMark, but it is clearly meant as one.
+    let mut bare_url_links = |link: BrokenLink<'_>| {
+        let reference = link.reference.trim();
+        BARE_URL_PREFIXES
+            .iter()
+            .any(|prefix| reference.starts_with(prefix))
+            .then(|| (CowStr::from(reference.to_string()), CowStr::from("")))
+    };
+
+    let parser =
+        Parser::new_with_broken_link_callback(markdown, options, Some(&mut bare_url_links));
     let mut tags_stack = Vec::new();
+    let mut links = Links::new(opts.links);
     let mut buffer = String::new();
 
     // For each event we push into the buffer to produce the plain text version.
@@ -15,11 +38,21 @@ pub fn to_plain_text(markdown: &str) -> String {
         match event {
             // The start and end events don't contain the text inside the tag. That's handled by the `Event::Text` arm.
             Event::Start(tag) => {
+                if matches!(tag, Tag::Link { .. }) {
+                    links.start(&buffer);
+                }
                 start_tag(&tag, &mut buffer, &mut tags_stack);
                 tags_stack.push(tag);
             }
             Event::End(tag) => {
-                tags_stack.pop();
+                if let Some(Tag::Link {
+                    link_type,
+                    dest_url,
+                    ..
+                }) = tags_stack.pop()
+                {
+                    links.end(link_type, &dest_url, &mut buffer);
+                }
                 end_tag(&tag, &mut buffer, &tags_stack);
             }
             Event::Text(content) => {
@@ -29,28 +62,89 @@ pub fn to_plain_text(markdown: &str) -> String {
             }
             Event::Code(content) => buffer.push_str(&content),
             Event::SoftBreak => buffer.push(' '),
+            Event::TaskListMarker(checked) => {
+                buffer.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            Event::FootnoteReference(label) => {
+                buffer.push('[');
+                buffer.push_str(&label);
+                buffer.push(']');
+            }
             _ => (),
         }
     }
-    buffer.trim().to_string()
+
+    let mut buffer = buffer.trim().to_string();
+    links.write_references(&mut buffer);
+    buffer
 }
 
-fn start_tag(tag: &Tag, buffer: &mut String, tags_stack: &mut Vec<Tag>) {
-    match tag {
-        Tag::Link {
-            link_type: _,
-            dest_url: _,
-            title,
-            id: _,
+// Keeps track of the links being rendered, and of their URLs when rendering them as references.
+struct Links {
+    style: LinkStyle,
+    text_starts: Vec<usize>,
+    references: Vec<String>,
+}
+
+impl Links {
+    fn new(style: LinkStyle) -> Self {
+        Links {
+            style,
+            text_starts: Vec::new(),
+            references: Vec::new(),
         }
-        | Tag::Image {
-            link_type: _,
-            dest_url: _,
-            title,
-            id: _,
-        } => {
-            buffer.push_str(title);
+    }
+
+    fn start(&mut self, buffer: &str) {
+        self.text_starts.push(buffer.len());
+    }
+
+    fn end(&mut self, link_type: LinkType, url: &str, buffer: &mut String) {
+        let text_start = self.text_starts.pop().unwrap_or(buffer.len());
+        let text = &buffer[text_start..];
+
+        // Autolinks (and links whose text is their URL) would only repeat themselves.
+        let is_autolink = matches!(link_type, LinkType::Autolink | LinkType::Email)
+            || text == url
+            || url.strip_prefix("mailto:") == Some(text);
+        if is_autolink || url.is_empty() {
+            return;
+        }
+
+        match self.style {
+            LinkStyle::Drop => (),
+            LinkStyle::Inline => {
+                buffer.push_str(" (");
+                buffer.push_str(url);
+                buffer.push(')');
+            }
+            LinkStyle::Footnote => {
+                let number = match self.references.iter().position(|r| r == url) {
+                    Some(index) => index + 1,
+                    None => {
+                        self.references.push(url.to_string());
+                        self.references.len()
+                    }
+                };
+                buffer.push_str(&format!(" [{}]", number));
+            }
+        }
+    }
+
+    fn write_references(&self, buffer: &mut String) {
+        if self.references.is_empty() {
+            return;
+        }
+
+        buffer.push('\n');
+        for (i, url) in self.references.iter().enumerate() {
+            buffer.push_str(&format!("\n[{}]: {}", i + 1, url));
         }
+    }
+}
+
+fn start_tag(tag: &Tag, buffer: &mut String, tags_stack: &mut Vec<Tag>) {
+    match tag {
         Tag::Item => {
             buffer.push('\n');
             let mut lists_stack = tags_stack
@@ -72,7 +166,17 @@ fn start_tag(tag: &Tag, buffer: &mut String, tags_stack: &mut Vec<Tag>) {
                 buffer.push_str("• ");
             }
         }
+        // Footnote definitions are rendered as `[label]: content`, so their first paragraph
+        // shouldn't start on a new line.
+        Tag::Paragraph if matches!(tags_stack.last(), Some(Tag::FootnoteDefinition(_))) => (),
+        Tag::FootnoteDefinition(label) => {
+            buffer.push('\n');
+            buffer.push('[');
+            buffer.push_str(label);
+            buffer.push_str("]: ");
+        }
         Tag::Paragraph
+        | Tag::Table(_)
         | Tag::CodeBlock(_)
         | Tag::Heading {
             level: _,
@@ -87,6 +191,19 @@ fn start_tag(tag: &Tag, buffer: &mut String, tags_stack: &mut Vec<Tag>) {
 fn end_tag(tag: &TagEnd, buffer: &mut String, tags_stack: &Vec<Tag>) {
     match tag {
         TagEnd::Paragraph | TagEnd::Heading(_) => buffer.push('\n'),
+        // Cells are separated by pipes, each row is on its own line. Empty leading cells (e.g.
+        // with only struck-through text) are left out rather than starting the row with a pipe.
+        TagEnd::TableCell => {
+            if !buffer.ends_with('\n') {
+                buffer.push_str(TABLE_CELL_SEPARATOR)
+            }
+        }
+        TagEnd::TableHead | TagEnd::TableRow => {
+            if buffer.ends_with(TABLE_CELL_SEPARATOR) {
+                buffer.truncate(buffer.len() - TABLE_CELL_SEPARATOR.len());
+            }
+            buffer.push('\n')
+        }
         TagEnd::CodeBlock => {
             if !buffer.ends_with('\n') {
                 buffer.push('\n');
@@ -107,7 +224,8 @@ fn is_strikethrough(tag: &Tag) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::to_plain_text;
+    use super::{to_plain_text, to_plain_text_with};
+    use crate::types::{LinkStyle, PlainTextOptions};
 
     #[test]
     fn basic_inline_strong() {
@@ -226,7 +344,6 @@ End paragraph.";
         assert_eq!(to_plain_text(markdown), expected)
     }
 
-    #[ignore]
     #[test]
     fn link_with_itself() {
         let markdown = "Go to [https://www.google.com].";
@@ -234,6 +351,39 @@ End paragraph.";
         assert_eq!(to_plain_text(markdown), expected)
     }
 
+    #[test]
+    fn autolink() {
+        let markdown = "Go to <https://www.google.com> or email <hello@example.com>.";
+        let expected = "Go to https://www.google.com or email hello@example.com.";
+        let opts = PlainTextOptions {
+            links: LinkStyle::Inline,
+        };
+        assert_eq!(to_plain_text_with(markdown, &opts), expected)
+    }
+
+    #[test]
+    fn inline_link_urls() {
+        let markdown = "I'm an [inline-style link](https://www.google.com \"Google\").";
+        let expected = "I'm an inline-style link (https://www.google.com).";
+        let opts = PlainTextOptions {
+            links: LinkStyle::Inline,
+        };
+        assert_eq!(to_plain_text_with(markdown, &opts), expected)
+    }
+
+    #[test]
+    fn footnote_link_urls() {
+        let markdown = "See [one](https://a.example), [two](https://b.example) and [one again](https://a.example).";
+        let expected = "See one [1], two [2] and one again [1].
+
+[1]: https://a.example
+[2]: https://b.example";
+        let opts = PlainTextOptions {
+            links: LinkStyle::Footnote,
+        };
+        assert_eq!(to_plain_text_with(markdown, &opts), expected)
+    }
+
     #[test]
     fn basic_image() {
         let markdown = "As displayed in ![img alt text](https://github.com/adam-p/markdown-here/raw/master/src/common/images/icon48.png).";
@@ -241,6 +391,13 @@ End paragraph.";
         assert_eq!(to_plain_text(markdown), expected);
     }
 
+    #[test]
+    fn image_with_title() {
+        let markdown = r#"![img alt text](https://example.com/icon.png "Icon title")"#;
+        let expected = "img alt text";
+        assert_eq!(to_plain_text(markdown), expected);
+    }
+
     #[test]
     fn inline_code() {
         let markdown = "This is `inline code`.";
@@ -281,6 +438,50 @@ End paragraph.";
         assert_eq!(to_plain_text(markdown), expected);
     }
 
+    #[test]
+    fn table() {
+        let markdown = r#"Start paragraph.
+
+| Name | Value |
+|------|------:|
+| `a`  | 1     |
+| ~~b~~ | 2    |
+
+End paragraph."#;
+        let expected = "Start paragraph.
+
+Name | Value
+a | 1
+2
+
+End paragraph.";
+        assert_eq!(to_plain_text(markdown), expected);
+    }
+
+    #[test]
+    fn task_list() {
+        let markdown = r#"
+- [x] Done
+- [ ] Not done
+    - [X] Nested
+"#;
+        let expected = "• [x] Done
+• [ ] Not done
+\t• [x] Nested";
+        assert_eq!(to_plain_text(markdown), expected);
+    }
+
+    #[test]
+    fn footnotes() {
+        let markdown = r#"Here is a note[^1].
+
+[^1]: The note itself."#;
+        let expected = "Here is a note[1].
+
+[1]: The note itself.";
+        assert_eq!(to_plain_text(markdown), expected);
+    }
+
     #[test]
     fn paragraphs() {
         let markdown = r#"Paragraph 1.
@@ -0,0 +1,292 @@
+use std::{collections::HashSet, ops::Range};
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::{
+    front_matter,
+    types::{CodeBlock, Heading, Image, Link, Outline},
+};
+
+/// Parses the Markdown (without its front matter) and returns its heading tree along with every
+/// link, image and code block.
+///
+/// Ranges are byte offsets in the whole Markdown, front matter included.
+pub fn outline(markdown: &str) -> Outline {
+    let (_, body) = front_matter::split(markdown);
+    let offset = markdown.len() - body.len();
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+
+    let mut outline = Outline::default();
+    let mut headings = Vec::new();
+    let mut anchors = Anchors::default();
+
+    // The headings, links, images and code blocks being parsed, with their range and the text
+    // collected so far. Links and images can be nested in headings and images in links, so each of
+    // them gets its own buffer and text goes to all of them.
+    let mut open: Vec<(Tag, Range<usize>, String)> = Vec::new();
+
+    for (event, range) in Parser::new_ext(body, options).into_offset_iter() {
+        let range = range.start + offset..range.end + offset;
+        match event {
+            Event::Start(
+                tag @ (Tag::Heading { .. }
+                | Tag::Link { .. }
+                | Tag::Image { .. }
+                | Tag::CodeBlock(_)),
+            ) => open.push((tag, range, String::new())),
+            Event::End(TagEnd::Heading(_) | TagEnd::Link | TagEnd::Image | TagEnd::CodeBlock) => {
+                let Some((tag, range, text)) = open.pop() else {
+                    continue;
+                };
+
+                match tag {
+                    Tag::Heading { level, id, .. } => {
+                        let text = text.trim().to_string();
+                        let anchor = match id {
+                            Some(id) => anchors.unique(&id),
+                            None => anchors.unique(&slugify(&text)),
+                        };
+                        headings.push(Heading {
+                            level: level as u8,
+                            text,
+                            anchor,
+                            section: range.clone(),
+                            range,
+                            children: Vec::new(),
+                        });
+                    }
+                    Tag::Link {
+                        dest_url, title, ..
+                    } => outline.links.push(Link {
+                        text,
+                        url: dest_url.to_string(),
+                        title: title.to_string(),
+                        range,
+                    }),
+                    Tag::Image {
+                        dest_url, title, ..
+                    } => outline.images.push(Image {
+                        alt: text,
+                        url: dest_url.to_string(),
+                        title: title.to_string(),
+                        range,
+                    }),
+                    Tag::CodeBlock(kind) => outline.code_blocks.push(CodeBlock {
+                        language: match kind {
+                            CodeBlockKind::Fenced(info) => {
+                                info.split_whitespace().next().map(|lang| lang.to_string())
+                            }
+                            CodeBlockKind::Indented => None,
+                        },
+                        code: text,
+                        range,
+                    }),
+                    _ => (),
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                for (_, _, buffer) in &mut open {
+                    buffer.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                for (_, _, buffer) in &mut open {
+                    buffer.push(' ');
+                }
+            }
+            _ => (),
+        }
+    }
+
+    outline.headings = nest(headings, markdown.len());
+    outline
+}
+
+/// Turns heading text into a URL fragment the way GitHub does: lowercased, with punctuation
+/// removed and spaces replaced by hyphens.
+pub(crate) fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            _ if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+// Hands out anchors that are unique within a document, by suffixing repeated ones with `-1`, `-2`
+// and so on like GitHub does.
+#[derive(Default)]
+pub(crate) struct Anchors {
+    used: HashSet<String>,
+}
+
+impl Anchors {
+    pub(crate) fn unique(&mut self, anchor: &str) -> String {
+        let mut candidate = anchor.to_string();
+        let mut suffix = 0;
+        while !self.used.insert(candidate.clone()) {
+            suffix += 1;
+            candidate = format!("{}-{}", anchor, suffix);
+        }
+        candidate
+    }
+}
+
+// Builds the heading tree from the headings in document order, and extends each heading's section
+// up to the next heading of the same or a higher level.
+fn nest(headings: Vec<Heading>, end: usize) -> Vec<Heading> {
+    let mut roots: Vec<Heading> = Vec::new();
+    let mut stack: Vec<Heading> = Vec::new();
+
+    for heading in headings {
+        while let Some(last) = stack.last()
+            && last.level >= heading.level
+        {
+            let mut closed = stack.pop().unwrap();
+            closed.section.end = heading.range.start;
+            attach(closed, &mut stack, &mut roots);
+        }
+        stack.push(heading);
+    }
+
+    while let Some(mut closed) = stack.pop() {
+        closed.section.end = end;
+        attach(closed, &mut stack, &mut roots);
+    }
+
+    roots
+}
+
+fn attach(heading: Heading, stack: &mut [Heading], roots: &mut Vec<Heading>) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(heading),
+        None => roots.push(heading),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_tree() {
+        let markdown =
+            "# Title\n\nIntro\n\n## First\n\nText\n\n### Nested\n\n## Second\n\n# Other\n";
+        let outline = outline(markdown);
+
+        assert_eq!(outline.headings.len(), 2);
+        let title = &outline.headings[0];
+        assert_eq!(title.level, 1);
+        assert_eq!(title.text, "Title");
+        assert_eq!(&markdown[title.range.clone()], "# Title\n");
+        assert_eq!(
+            &markdown[title.section.clone()],
+            "# Title\n\nIntro\n\n## First\n\nText\n\n### Nested\n\n## Second\n\n"
+        );
+
+        let children = title
+            .children
+            .iter()
+            .map(|h| h.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(children, vec!["First", "Second"]);
+        assert_eq!(title.children[0].children[0].text, "Nested");
+        assert_eq!(
+            &markdown[title.children[0].section.clone()],
+            "## First\n\nText\n\n### Nested\n\n"
+        );
+        assert_eq!(outline.headings[1].section.end, markdown.len());
+    }
+
+    #[test]
+    fn test_heading_anchors() {
+        let outline =
+            outline("# Hello, *World*!\n\n# Hello World\n\n# Hello World\n\n# Custom {#my-id}\n");
+        let anchors = outline
+            .headings
+            .iter()
+            .map(|h| h.anchor.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            anchors,
+            vec!["hello-world", "hello-world-1", "hello-world-2", "my-id"]
+        );
+        assert_eq!(outline.headings[0].text, "Hello, World!");
+    }
+
+    #[test]
+    fn test_links_and_images() {
+        let markdown = "See [the docs](https://example.com \"Docs\") and <https://rust-lang.org>.\n\n[![Logo](logo.png)](https://example.com)";
+        let outline = outline(markdown);
+
+        let links = outline
+            .links
+            .iter()
+            .map(|l| (l.text.as_str(), l.url.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                ("the docs", "https://example.com"),
+                ("https://rust-lang.org", "https://rust-lang.org"),
+                ("Logo", "https://example.com"),
+            ]
+        );
+        assert_eq!(outline.links[0].title, "Docs");
+        assert_eq!(
+            &markdown[outline.links[0].range.clone()],
+            "[the docs](https://example.com \"Docs\")"
+        );
+
+        assert_eq!(outline.images.len(), 1);
+        assert_eq!(outline.images[0].alt, "Logo");
+        assert_eq!(outline.images[0].url, "logo.png");
+    }
+
+    #[test]
+    fn test_links_in_headings() {
+        let outline = outline("# See [the docs](x) and ![a logo](logo.png)\n");
+
+        assert_eq!(outline.headings[0].text, "See the docs and a logo");
+        assert_eq!(outline.headings[0].anchor, "see-the-docs-and-a-logo");
+        assert_eq!(outline.links[0].text, "the docs");
+        assert_eq!(outline.images[0].alt, "a logo");
+    }
+
+    #[test]
+    fn test_skips_front_matter() {
+        let markdown = "---\ntitle: x\n---\n# Title\n\nText\n";
+        let outline = outline(markdown);
+
+        assert_eq!(outline.headings.len(), 1);
+        assert_eq!(outline.headings[0].text, "Title");
+        assert_eq!(&markdown[outline.headings[0].range.clone()], "# Title\n");
+        assert_eq!(
+            &markdown[outline.headings[0].section.clone()],
+            "# Title\n\nText\n"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let markdown = "```rust title=main.rs\nfn main() {}\n```\n\n    indented\n";
+        let outline = outline(markdown);
+
+        assert_eq!(outline.code_blocks.len(), 2);
+        assert_eq!(outline.code_blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(outline.code_blocks[0].code, "fn main() {}\n");
+        assert_eq!(outline.code_blocks[1].language, None);
+        assert_eq!(outline.code_blocks[1].code, "indented\n");
+    }
+}
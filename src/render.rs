@@ -0,0 +1,191 @@
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd, html};
+
+use crate::{
+    front_matter,
+    outline::{Anchors, slugify},
+    types::{HtmlOptions, RawHtml},
+};
+
+const UNSAFE_URL_SCHEMES: &[&str] = &["javascript:", "vbscript:", "data:"];
+
+/// Renders the Markdown (without its front matter) to HTML with the GitHub Flavored Markdown
+/// extensions enabled.
+pub fn to_html(markdown: &str, opts: &HtmlOptions) -> String {
+    let (_, body) = front_matter::split(markdown);
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+    options.insert(Options::ENABLE_GFM);
+
+    let mut events = Parser::new_ext(body, options)
+        .filter_map(|event| sanitize(event, opts.raw_html))
+        .collect::<Vec<_>>();
+
+    if opts.heading_anchors {
+        add_heading_anchors(&mut events);
+    }
+
+    let mut output = String::with_capacity(body.len() * 3 / 2);
+    html::push_html(&mut output, events.into_iter());
+    output
+}
+
+fn sanitize(event: Event<'_>, raw_html: RawHtml) -> Option<Event<'_>> {
+    if raw_html == RawHtml::Allow {
+        return Some(event);
+    }
+
+    match event {
+        Event::Html(html) | Event::InlineHtml(html) => match raw_html {
+            RawHtml::Strip => None,
+            _ => Some(Event::Text(html)),
+        },
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Some(Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url, false),
+            title,
+            id,
+        })),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Some(Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url, true),
+            title,
+            id,
+        })),
+        _ => Some(event),
+    }
+}
+
+fn safe_url(url: CowStr<'_>, is_image: bool) -> CowStr<'_> {
+    // Browsers ignore whitespace and control characters in the scheme.
+    let scheme = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let is_unsafe = UNSAFE_URL_SCHEMES
+        .iter()
+        .any(|unsafe_scheme| scheme.starts_with(unsafe_scheme))
+        && !(is_image && scheme.starts_with("data:image/"));
+
+    if is_unsafe { CowStr::from("#") } else { url }
+}
+
+fn add_heading_anchors(events: &mut [Event<'_>]) {
+    let mut anchors = Anchors::default();
+
+    for i in 0..events.len() {
+        let Event::Start(Tag::Heading { id, .. }) = &events[i] else {
+            continue;
+        };
+
+        let anchor = match id {
+            Some(id) => anchors.unique(id),
+            None => {
+                let text = events[i + 1..]
+                    .iter()
+                    .take_while(|event| !matches!(event, Event::End(TagEnd::Heading(_))))
+                    .filter_map(|event| match event {
+                        Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<String>();
+                anchors.unique(&slugify(&text))
+            }
+        };
+
+        if let Event::Start(Tag::Heading { id, .. }) = &mut events[i] {
+            *id = Some(anchor.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gfm_extensions() {
+        let markdown =
+            "| a | b |\n|---|---|\n| 1 | 2 |\n\n- [x] done\n\n~~old~~ new[^1]\n\n[^1]: Note.";
+        let html = to_html(markdown, &HtmlOptions::default());
+
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<td>1</td>"));
+        assert!(html.contains("<input disabled=\"\" type=\"checkbox\" checked=\"\"/>"));
+        assert!(html.contains("<del>old</del>"));
+        assert!(html.contains("class=\"footnote-definition\""));
+    }
+
+    #[test]
+    fn test_heading_anchors() {
+        let markdown = "# Hello, `World`!\n\n## Hello World\n\n## Custom {#my-id}";
+        assert_eq!(
+            to_html(markdown, &HtmlOptions::default()),
+            "<h1 id=\"hello-world\">Hello, <code>World</code>!</h1>\n<h2 id=\"hello-world-1\">Hello World</h2>\n<h2 id=\"my-id\">Custom</h2>\n"
+        );
+
+        let opts = HtmlOptions {
+            heading_anchors: false,
+            ..Default::default()
+        };
+        assert_eq!(to_html("# Hello", &opts), "<h1>Hello</h1>\n");
+    }
+
+    #[test]
+    fn test_raw_html() {
+        let markdown = "Hi <b>there</b>\n\n<script>alert(1)</script>";
+        let html = |raw_html| {
+            to_html(
+                markdown,
+                &HtmlOptions {
+                    heading_anchors: true,
+                    raw_html,
+                },
+            )
+        };
+
+        assert_eq!(
+            html(RawHtml::Allow),
+            "<p>Hi <b>there</b></p>\n<script>alert(1)</script>"
+        );
+        assert_eq!(
+            html(RawHtml::Escape),
+            "<p>Hi &lt;b&gt;there&lt;/b&gt;</p>\n&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+        assert_eq!(html(RawHtml::Strip), "<p>Hi there</p>\n");
+    }
+
+    #[test]
+    fn test_unsafe_urls() {
+        let markdown = "[a](javascript:alert(1)) [b](JavaScript&#58;alert(1)) ![c](data:image/png;base64,AA==) [d](https://example.com)";
+        assert_eq!(
+            to_html(markdown, &HtmlOptions::default()),
+            "<p><a href=\"#\">a</a> <a href=\"#\">b</a> <img src=\"data:image/png;base64,AA==\" alt=\"c\" /> <a href=\"https://example.com\">d</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_skips_front_matter() {
+        assert_eq!(
+            to_html("---\ntitle: Hi\n---\nBody", &HtmlOptions::default()),
+            "<p>Body</p>\n"
+        );
+    }
+}
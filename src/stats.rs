@@ -0,0 +1,201 @@
+// Text statistics: counts, reading time and readability formulas.
+
+use crate::{
+    chunker,
+    language::{self, Language},
+    tokenizer::is_cjk,
+    types::{Readability, TextStats},
+};
+
+// The average silent reading speed of adults, from Brysbaert's 2019 meta-analysis.
+const WORDS_PER_MINUTE: f64 = 238.0;
+
+// Words longer than this count as long words for LIX.
+const LIX_LONG_WORD: usize = 6;
+
+const VOWELS: &str = "aeiouyàáâãäåæèéêëìíîïòóôõöøùúûüýÿœаеёиоуыэюя";
+
+/// Computes the statistics of the plain text.
+pub(crate) fn stats(text: &str) -> TextStats {
+    let paragraphs = chunker::by_paragraph(text);
+    let sentences = paragraphs
+        .iter()
+        .map(|paragraph| chunker::by_sentence(&paragraph.text).len())
+        .sum::<usize>();
+    let words = words(text);
+    let language = language::detect_lang(text);
+
+    let reading_time_seconds = (words.len() as f64 / WORDS_PER_MINUTE * 60.0).round() as u64;
+    let readability = (!words.is_empty() && sentences > 0).then(|| {
+        let has_lix = language::is_european_alphabet(text);
+        readability(&words, sentences, language, has_lix)
+    });
+
+    TextStats {
+        words: words.len(),
+        sentences,
+        paragraphs: paragraphs.len(),
+        characters: text.chars().filter(|c| !c.is_whitespace()).count(),
+        reading_time_seconds,
+        language,
+        readability,
+    }
+}
+
+fn readability(words: &[&str], sentences: usize, language: Language, has_lix: bool) -> Readability {
+    let word_count = words.len() as f64;
+    let words_per_sentence = word_count / sentences as f64;
+    let syllables_per_word = words
+        .iter()
+        .map(|word| syllables(word, language))
+        .sum::<usize>() as f64
+        / word_count;
+
+    // Each adaptation re-weights sentence and word length for the language.
+    let weights = match language {
+        Language::English => Some((206.835, 1.015, 84.6)),
+        Language::German => Some((180.0, 1.0, 58.5)),
+        Language::French => Some((207.0, 1.015, 73.6)),
+        Language::Spanish => Some((206.84, 1.02, 60.0)),
+        Language::Italian => Some((217.0, 1.3, 60.0)),
+        Language::Dutch => Some((206.835, 0.93, 77.0)),
+        Language::Portuguese => Some((248.835, 1.015, 84.6)),
+        Language::Russian => Some((206.835, 1.3, 60.1)),
+        _ => None,
+    };
+    let flesch_reading_ease = weights.map(|(base, sentence_weight, syllable_weight)| {
+        let score =
+            base - sentence_weight * words_per_sentence - syllable_weight * syllables_per_word;
+        score.clamp(0.0, 100.0)
+    });
+
+    let flesch_kincaid_grade = matches!(language, Language::English)
+        .then(|| 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59);
+
+    // Word length means nothing in scripts without spaces (e.g. CJK) or with few letters per word.
+    let lix = has_lix.then(|| {
+        let long_words = words
+            .iter()
+            .filter(|word| word.chars().count() > LIX_LONG_WORD)
+            .count() as f64;
+        words_per_sentence + 100.0 * long_words / word_count
+    });
+
+    Readability {
+        flesch_reading_ease,
+        flesch_kincaid_grade,
+        lix,
+    }
+}
+
+// Splits the text into words, with each CJK character as its own word.
+fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for token in text.split_whitespace() {
+        if !token.contains(is_cjk) {
+            if token.chars().any(char::is_alphanumeric) {
+                words.push(token.trim_matches(|c: char| !c.is_alphanumeric()));
+            }
+            continue;
+        }
+
+        let mut start = None;
+        for (i, c) in token.char_indices() {
+            if is_cjk(c) {
+                if let Some(start) = start.take() {
+                    words.push(&token[start..i]);
+                }
+                words.push(&token[i..i + c.len_utf8()]);
+            } else if c.is_alphanumeric() {
+                start.get_or_insert(i);
+            } else if let Some(start) = start.take() {
+                words.push(&token[start..i]);
+            }
+        }
+        if let Some(start) = start {
+            words.push(&token[start..]);
+        }
+    }
+    words
+}
+
+// Estimates syllables by counting groups of vowels, which is close enough for the formulas.
+fn syllables(word: &str, language: Language) -> usize {
+    let word = word.to_lowercase();
+    let mut count = 0;
+    let mut previous_is_vowel = false;
+    for c in word.chars() {
+        let is_vowel = VOWELS.contains(c);
+        if is_vowel && !previous_is_vowel {
+            count += 1;
+        }
+        previous_is_vowel = is_vowel;
+    }
+
+    // A final silent "e" doesn't make a syllable in English ("make"), unless after an "l" ("able").
+    let is_silent_e = matches!(language, Language::English)
+        && word.ends_with('e')
+        && !word.ends_with("le")
+        && !word.ends_with("ee");
+    if is_silent_e && count > 1 {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let text = "The cat sat on the mat. It was a sunny day, and the cat was happy.\n\n\
+            The dog came over to play with the cat in the garden.";
+        let stats = stats(text);
+
+        assert_eq!(stats.words, 28);
+        assert_eq!(stats.sentences, 3);
+        assert_eq!(stats.paragraphs, 2);
+        assert_eq!(stats.reading_time_seconds, 7);
+        assert!(matches!(stats.language, Language::English));
+
+        let readability = stats.readability.unwrap();
+        assert!(readability.flesch_reading_ease.unwrap() > 90.0);
+        assert!(readability.flesch_kincaid_grade.unwrap() < 3.0);
+        assert!(readability.lix.unwrap() < 20.0);
+    }
+
+    #[test]
+    fn test_stats_without_alphabet() {
+        let stats = stats("今天天气很好。我们去公园散步吧。");
+        let readability = stats.readability.unwrap();
+
+        assert_eq!(readability.lix, None);
+        assert_eq!(readability.flesch_reading_ease, None);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let stats = stats("");
+        assert_eq!(stats.words, 0);
+        assert_eq!(stats.sentences, 0);
+        assert!(stats.readability.is_none());
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            words("Hello, world! 你好世界 — ok"),
+            vec!["Hello", "world", "你", "好", "世", "界", "ok"]
+        );
+    }
+
+    #[test]
+    fn test_syllables() {
+        assert_eq!(syllables("make", Language::English), 1);
+        assert_eq!(syllables("table", Language::English), 2);
+        assert_eq!(syllables("readability", Language::English), 5);
+        assert_eq!(syllables("Kaffee", Language::German), 2);
+    }
+}
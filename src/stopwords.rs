@@ -0,0 +1,335 @@
+// Stopwords (the most common function words) for the languages supported by `language::Language`,
+// used to ignore words that carry no meaning on their own when ranking sentences and keywords.
+// Languages without a list here are handled without stopwords.
+
+use crate::language::Language;
+
+const ENGLISH: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "even",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "may",
+    "me",
+    "might",
+    "more",
+    "most",
+    "much",
+    "must",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "one",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "shall",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "us",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "yet",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "am", "an", "ander",
+    "andere", "anderen", "auch", "auf", "aus", "bei", "bin", "bis", "bist", "da", "damit", "dann",
+    "das", "dass", "dein", "dem", "den", "denn", "der", "des", "dich", "die", "dies", "diese",
+    "diesem", "diesen", "dieser", "dieses", "dir", "doch", "dort", "du", "durch", "ein", "eine",
+    "einem", "einen", "einer", "eines", "er", "es", "etwas", "euch", "euer", "für", "gegen",
+    "gewesen", "hab", "habe", "haben", "hat", "hatte", "hier", "hin", "hinter", "ich", "ihm",
+    "ihn", "ihnen", "ihr", "ihre", "im", "in", "indem", "ins", "ist", "jede", "jeder", "jedes",
+    "jetzt", "kann", "kein", "keine", "können", "man", "manche", "mein", "mich", "mir", "mit",
+    "muss", "nach", "nicht", "nichts", "noch", "nun", "nur", "ob", "oder", "ohne", "sehr", "sein",
+    "seine", "sich", "sie", "sind", "so", "solche", "soll", "sondern", "um", "und", "uns", "unser",
+    "unter", "viel", "vom", "von", "vor", "war", "waren", "warum", "was", "weil", "welche", "wenn",
+    "werden", "wie", "wieder", "will", "wir", "wird", "wo", "wurde", "zu", "zum", "zur",
+    "zwischen",
+];
+
+const FRENCH: &[&str] = &[
+    "a", "ai", "au", "aussi", "aux", "avec", "avoir", "c", "ce", "ceci", "cela", "ces", "cet",
+    "cette", "d", "dans", "de", "des", "donc", "du", "elle", "elles", "en", "est", "et", "été",
+    "être", "eu", "il", "ils", "j", "je", "l", "la", "le", "les", "leur", "leurs", "lui", "m",
+    "ma", "mais", "me", "même", "mes", "moi", "mon", "n", "ne", "nos", "notre", "nous", "on",
+    "ont", "ou", "où", "par", "pas", "plus", "pour", "qu", "que", "qui", "s", "sa", "sans", "se",
+    "ses", "si", "son", "sont", "sur", "t", "ta", "te", "tes", "toi", "ton", "tout", "tous", "tu",
+    "un", "une", "vos", "votre", "vous", "y",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "algo", "como", "con", "contra", "cual", "cuando", "de", "del", "desde", "donde",
+    "durante", "e", "el", "ella", "ellas", "ellos", "en", "entre", "era", "es", "esa", "ese",
+    "eso", "esta", "está", "están", "este", "esto", "fue", "ha", "han", "hasta", "hay", "la",
+    "las", "le", "les", "lo", "los", "más", "me", "mi", "muy", "nada", "ni", "no", "nos",
+    "nosotros", "o", "otra", "otro", "para", "pero", "poco", "por", "porque", "que", "qué", "se",
+    "sea", "ser", "si", "sí", "sin", "sobre", "son", "su", "sus", "también", "te", "tiene", "todo",
+    "todos", "tu", "un", "una", "uno", "unos", "y", "ya", "yo",
+];
+
+const ITALIAN: &[&str] = &[
+    "a", "ad", "al", "alla", "alle", "anche", "che", "chi", "ci", "come", "con", "cui", "da",
+    "dal", "dalla", "degli", "dei", "del", "della", "delle", "di", "dove", "e", "è", "ed", "gli",
+    "ha", "hanno", "i", "il", "in", "io", "la", "le", "lei", "lo", "loro", "lui", "ma", "mi",
+    "mio", "ne", "nei", "nel", "nella", "noi", "non", "o", "per", "perché", "più", "quale",
+    "quando", "quella", "quello", "questa", "questo", "se", "si", "sia", "sono", "su", "sua",
+    "sue", "sui", "sul", "sulla", "suo", "tra", "tu", "tutto", "un", "una", "uno", "voi",
+];
+
+const PORTUGUESE: &[&str] = &[
+    "a", "ao", "aos", "as", "com", "como", "da", "das", "de", "dela", "dele", "do", "dos", "e",
+    "é", "ela", "elas", "ele", "eles", "em", "entre", "era", "essa", "esse", "esta", "está",
+    "este", "eu", "foi", "há", "isso", "isto", "já", "lhe", "mais", "mas", "me", "mesmo", "meu",
+    "minha", "muito", "na", "nas", "não", "nem", "no", "nos", "nós", "num", "numa", "o", "os",
+    "ou", "para", "pela", "pelo", "por", "quando", "que", "quem", "se", "sem", "ser", "seu", "sua",
+    "são", "também", "te", "tem", "um", "uma", "você",
+];
+
+const DUTCH: &[&str] = &[
+    "aan", "al", "alles", "als", "bij", "dan", "dat", "de", "der", "deze", "die", "dit", "doch",
+    "door", "dus", "een", "en", "er", "ge", "geen", "had", "heb", "hebben", "heeft", "hem", "het",
+    "hier", "hij", "hoe", "hun", "ik", "in", "is", "ja", "je", "kan", "maar", "me", "meer", "men",
+    "met", "mij", "mijn", "na", "naar", "niet", "niets", "nog", "nu", "of", "om", "omdat", "ons",
+    "ook", "op", "over", "reeds", "te", "tegen", "toch", "toen", "tot", "u", "uit", "van", "veel",
+    "voor", "want", "waren", "was", "wat", "we", "wel", "werd", "wie", "wij", "wordt", "zal", "ze",
+    "zich", "zij", "zijn", "zo", "zou",
+];
+
+const SWEDISH: &[&str] = &[
+    "alla", "att", "av", "blev", "bli", "de", "dem", "den", "denna", "deras", "det", "detta",
+    "dig", "du", "där", "efter", "ej", "eller", "en", "er", "ett", "från", "för", "ha", "hade",
+    "han", "hans", "har", "henne", "hon", "honom", "hur", "här", "i", "icke", "ingen", "inom",
+    "inte", "jag", "kan", "man", "med", "mellan", "men", "mig", "min", "mot", "mycket", "ni", "nu",
+    "när", "och", "om", "oss", "på", "sig", "sin", "sina", "sitt", "som", "så", "till", "under",
+    "upp", "ut", "var", "vara", "vi", "vid", "vilka", "vilken", "än", "är", "över",
+];
+
+const DANISH: &[&str] = &[
+    "af", "alle", "at", "blev", "blive", "da", "de", "dem", "den", "denne", "der", "deres", "det",
+    "dette", "dig", "din", "du", "efter", "eller", "en", "end", "er", "et", "for", "fra", "ham",
+    "han", "hans", "har", "havde", "have", "hende", "hun", "hvad", "hvis", "hvor", "i", "ikke",
+    "ind", "jeg", "kan", "man", "med", "meget", "men", "mig", "min", "mod", "ned", "noget", "nu",
+    "når", "og", "også", "om", "op", "os", "over", "på", "sig", "sin", "skal", "som", "til", "ud",
+    "under", "var", "vi", "vil", "være", "været",
+];
+
+const NORWEGIAN: &[&str] = &[
+    "alle", "at", "av", "bare", "da", "de", "dei", "dem", "den", "denne", "der", "det", "dette",
+    "di", "din", "du", "eller", "en", "enn", "er", "et", "ett", "etter", "for", "fra", "ha",
+    "hadde", "han", "hans", "har", "hennes", "hun", "hva", "hvis", "hvor", "i", "ikke", "inn",
+    "jeg", "kan", "man", "med", "men", "mot", "mye", "nå", "når", "og", "også", "om", "opp", "oss",
+    "over", "på", "seg", "sin", "skal", "som", "så", "til", "ut", "var", "vi", "vil", "være",
+    "vært",
+];
+
+const FINNISH: &[&str] = &[
+    "ei", "eivät", "en", "et", "he", "hän", "ja", "jo", "joka", "jos", "kanssa", "kuin", "kun",
+    "me", "mikä", "minä", "mitä", "mutta", "myös", "ne", "niin", "no", "nyt", "ole", "olen", "oli",
+    "olivat", "olla", "on", "ovat", "se", "sekä", "sen", "sinä", "tai", "te", "tämä", "tässä",
+    "vaan", "vai", "vielä", "voi",
+];
+
+const RUSSIAN: &[&str] = &[
+    "а",
+    "без",
+    "бы",
+    "был",
+    "была",
+    "были",
+    "было",
+    "быть",
+    "в",
+    "вам",
+    "вас",
+    "весь",
+    "во",
+    "вот",
+    "все",
+    "всё",
+    "вы",
+    "где",
+    "да",
+    "для",
+    "до",
+    "его",
+    "ее",
+    "её",
+    "если",
+    "есть",
+    "ещё",
+    "же",
+    "за",
+    "здесь",
+    "и",
+    "из",
+    "или",
+    "им",
+    "их",
+    "к",
+    "как",
+    "когда",
+    "кто",
+    "ли",
+    "мне",
+    "мы",
+    "на",
+    "над",
+    "не",
+    "нет",
+    "ни",
+    "но",
+    "о",
+    "об",
+    "он",
+    "она",
+    "они",
+    "оно",
+    "от",
+    "по",
+    "под",
+    "при",
+    "с",
+    "со",
+    "так",
+    "также",
+    "то",
+    "только",
+    "тот",
+    "у",
+    "уже",
+    "что",
+    "это",
+    "этот",
+    "я",
+];
+
+/// Returns the stopwords of the language, lowercase, or an empty list if there are none for it.
+pub(crate) fn stopwords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => ENGLISH,
+        Language::German => GERMAN,
+        Language::French => FRENCH,
+        Language::Spanish => SPANISH,
+        Language::Italian => ITALIAN,
+        Language::Portuguese => PORTUGUESE,
+        Language::Dutch => DUTCH,
+        Language::Swedish => SWEDISH,
+        Language::Danish => DANISH,
+        Language::Norwegian => NORWEGIAN,
+        Language::Finnish => FINNISH,
+        Language::Russian => RUSSIAN,
+        _ => &[],
+    }
+}
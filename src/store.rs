@@ -1,6 +1,8 @@
+use base64::Engine;
+
 use crate::{
-    allocator,
-    error::{Error, StoreError},
+    allocator, crypto,
+    error::{CryptoError, Error, StoreError},
     host, safe_alloc, store_capnp, types,
 };
 
@@ -94,3 +96,73 @@ pub fn clear() -> Result<(), Error> {
     unsafe { host::store_clear(0, 0) };
     Ok(())
 }
+
+/// A wrapper around the store that transparently encrypts values before they are written and
+/// decrypts them when they are read back.
+///
+/// Values are sealed with [`crypto::seal_with_aad`] using the key name as additional data, so an
+/// encrypted value cannot be moved to another key without failing to open. Keys themselves are
+/// stored in plain text.
+#[derive(Debug, Clone)]
+pub struct EncryptedStore {
+    key: crypto::SecretKey,
+}
+
+impl EncryptedStore {
+    pub fn new(key: crypto::SecretKey) -> Self {
+        EncryptedStore { key }
+    }
+
+    /// Creates an encrypted store using the plugin's host-provided key.
+    pub fn with_plugin_key() -> Result<Self, Error> {
+        Ok(Self::new(crypto::SecretKey::from_host()?))
+    }
+
+    /// Gets and decrypts a value from the store by its key.
+    pub fn get(&self, key: &str) -> Result<String, Error> {
+        let sealed = get(key)?;
+        self.open(key, &sealed)
+    }
+
+    /// Encrypts and sets a value in the store for the current plugin.
+    ///
+    /// Unlike [`set`], this does not return the previous value.
+    pub fn set(&self, key: &str, value: &str) -> Result<(), Error> {
+        let sealed = crypto::seal_with_aad(&self.key, value.as_bytes(), key.as_bytes())?;
+        set(
+            key,
+            &base64::engine::general_purpose::STANDARD.encode(sealed),
+        )?;
+        Ok(())
+    }
+
+    /// Deletes a value from the store by its key.
+    pub fn delete(&self, key: &str) -> Result<(), Error> {
+        delete(key)
+    }
+
+    /// Gets and decrypts all key-value pairs from the store.
+    ///
+    /// Values that cannot be decrypted with this store's key (e.g. values written with
+    /// [`set`] directly) are skipped.
+    pub fn all(&self) -> Result<Vec<(String, String)>, Error> {
+        let pairs = all()?
+            .into_iter()
+            .filter_map(|(key, sealed)| {
+                let value = self.open(&key, &sealed).ok()?;
+                Some((key, value))
+            })
+            .collect();
+
+        Ok(pairs)
+    }
+
+    fn open(&self, key: &str, sealed: &str) -> Result<String, Error> {
+        let sealed = base64::engine::general_purpose::STANDARD
+            .decode(sealed)
+            .map_err(|_| Error::CryptoError(CryptoError::DecryptionFailed))?;
+        let value = crypto::open_with_aad(&self.key, &sealed, key.as_bytes())?;
+
+        String::from_utf8(value).map_err(|e| Error::Utf8(e.utf8_error()))
+    }
+}
@@ -0,0 +1,207 @@
+// Extractive summarisation with TextRank: sentences are the nodes of a graph, weighted by how
+// many (non-stop) words they share, and the sentences PageRank ranks highest are the summary.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    chunker,
+    keywords::{stopword_set, words},
+};
+
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 50;
+const CONVERGENCE: f64 = 1e-6;
+// The most sentences ranked, the graph can have up to the square of this many edges.
+const MAX_SENTENCES: usize = 1_000;
+
+/// Returns the `n_sentences` most representative sentences of the text, in the order they
+/// appear in it.
+pub(crate) fn summarize(text: &str, n_sentences: usize) -> Vec<String> {
+    let sentences = chunker::by_paragraph(text)
+        .iter()
+        .flat_map(|paragraph| chunker::by_sentence(&paragraph.text))
+        .map(|sentence| {
+            sentence
+                .text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|sentence| !sentence.is_empty())
+        .collect::<Vec<_>>();
+    if sentences.len() <= n_sentences {
+        return sentences;
+    }
+
+    let stopwords = stopword_set(text);
+    let bags = sentences
+        .iter()
+        .map(|sentence| {
+            words(sentence)
+                .filter(|word| !stopwords.contains(word.as_str()))
+                .collect::<HashSet<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let candidates = candidates(&bags);
+    let edges = edges(&candidates.iter().map(|i| &bags[*i]).collect::<Vec<_>>());
+    let scores = rank(&edges);
+
+    let mut selected = (0..candidates.len()).collect::<Vec<_>>();
+    // Ties go to the earlier sentence, which tends to introduce the topic.
+    selected.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b)));
+    selected.truncate(n_sentences);
+    selected.sort();
+
+    selected
+        .into_iter()
+        .map(|i| sentences[candidates[i]].clone())
+        .collect()
+}
+
+// Returns the indices of the sentences to rank. Long texts are cut down to the sentences whose
+// words are the most common in the text, as those are the ones with the most (and heaviest)
+// edges, to keep the graph small enough.
+fn candidates(bags: &[HashSet<String>]) -> Vec<usize> {
+    if bags.len() <= MAX_SENTENCES {
+        return (0..bags.len()).collect();
+    }
+
+    let mut frequencies = HashMap::<&str, usize>::new();
+    for word in bags.iter().flatten() {
+        *frequencies.entry(word).or_default() += 1;
+    }
+
+    let weights = bags
+        .iter()
+        .map(|bag| {
+            let shared = bag
+                .iter()
+                .map(|word| frequencies[word.as_str()] - 1)
+                .sum::<usize>();
+            shared as f64 / (bag.len() as f64).ln().max(1.0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut candidates = (0..bags.len()).collect::<Vec<_>>();
+    candidates.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]).then(a.cmp(b)));
+    candidates.truncate(MAX_SENTENCES);
+    candidates.sort();
+    candidates
+}
+
+// Returns the adjacency list of the sentence graph, with only the sentences that share words
+// connected so that sparse graphs stay cheap.
+fn edges(bags: &[&HashSet<String>]) -> Vec<Vec<(usize, f64)>> {
+    let mut postings = HashMap::<&str, Vec<usize>>::new();
+    for (i, bag) in bags.iter().enumerate() {
+        for word in bag.iter() {
+            postings.entry(word).or_default().push(i);
+        }
+    }
+
+    let mut edges = vec![Vec::new(); bags.len()];
+    for (i, bag) in bags.iter().enumerate() {
+        let mut shared = HashMap::<usize, usize>::new();
+        for word in bag.iter() {
+            // Postings are sorted, so each pair is only counted from its first sentence.
+            for j in postings[word.as_str()].iter().filter(|j| **j > i) {
+                *shared.entry(*j).or_default() += 1;
+            }
+        }
+
+        for (j, shared) in shared {
+            let weight = similarity(shared, bag.len(), bags[j].len());
+            edges[i].push((j, weight));
+            edges[j].push((i, weight));
+        }
+    }
+
+    edges
+}
+
+// The similarity used by the TextRank paper: shared words, normalised by the sentence lengths so
+// long sentences aren't favoured just for being long.
+fn similarity(shared: usize, a: usize, b: usize) -> f64 {
+    let norm = (a as f64).ln() + (b as f64).ln();
+    shared as f64 / norm.max(1.0)
+}
+
+// Weighted PageRank over a symmetric graph.
+fn rank(edges: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let totals = edges
+        .iter()
+        .map(|neighbours| neighbours.iter().map(|(_, weight)| weight).sum::<f64>())
+        .collect::<Vec<_>>();
+    let mut scores = vec![1.0; edges.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut next = vec![1.0 - DAMPING; edges.len()];
+        for (j, neighbours) in edges.iter().enumerate() {
+            if totals[j] > 0.0 {
+                for (i, weight) in neighbours {
+                    next[*i] += DAMPING * weight / totals[j] * scores[j];
+                }
+            }
+        }
+
+        let change = next
+            .iter()
+            .zip(&scores)
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f64>();
+        scores = next;
+        if change < CONVERGENCE {
+            break;
+        }
+    }
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let text = "Rust is a systems programming language focused on safety. \
+            The weather was nice yesterday.\n\n\
+            The Rust compiler checks memory safety at compile time. \
+            Safety in Rust comes from ownership and borrowing, checked by the compiler. \
+            My cat likes to sleep all day.";
+
+        assert_eq!(
+            summarize(text, 2),
+            vec![
+                "The Rust compiler checks memory safety at compile time.",
+                "Safety in Rust comes from ownership and borrowing, checked by the compiler."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_short_text() {
+        assert_eq!(
+            summarize("Only one sentence.", 3),
+            vec!["Only one sentence."]
+        );
+        assert!(summarize("", 3).is_empty());
+        assert!(summarize("One. Two.", 0).is_empty());
+    }
+
+    #[test]
+    fn test_summarize_long_text() {
+        let sentences = (0..20_000)
+            .map(|i| format!("Memory safety note {i} about topic {}.", i % 50))
+            .collect::<Vec<_>>();
+
+        let summary = summarize(&sentences.join(" "), 3);
+        assert_eq!(summary.len(), 3);
+        let positions = summary
+            .iter()
+            .map(|sentence| sentences.iter().position(|s| s == sentence).unwrap())
+            .collect::<Vec<_>>();
+        assert!(positions.is_sorted());
+    }
+}
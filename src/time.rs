@@ -0,0 +1,11 @@
+use crate::host;
+
+/// Returns the current UNIX timestamp in milliseconds, as reported by the host.
+pub fn now_millis() -> u64 {
+    unsafe { host::time_now(0, 0) }
+}
+
+/// Returns the current UNIX timestamp in seconds, as reported by the host.
+pub fn now_secs() -> u64 {
+    now_millis() / 1000
+}
@@ -0,0 +1,163 @@
+use std::ops::Range;
+
+/// Counts tokens the way an embedding (or language) model would, so chunks can be sized to fit
+/// the model's limits.
+///
+/// Implement this for the tokenizer of the model you are targeting to get exact counts, or use
+/// [`ApproxTokenizer`] when an estimate is good enough. Any `Fn(&str) -> usize` is also a
+/// tokenizer.
+pub trait Tokenizer {
+    /// Returns the number of tokens the text is encoded as.
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+impl<F> Tokenizer for F
+where
+    F: Fn(&str) -> usize,
+{
+    fn count_tokens(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// A tokenizer that approximates byte-pair encoding (BPE) tokenizers such as the ones used by
+/// OpenAI's embedding models, without shipping a vocabulary.
+///
+/// Latin words are counted as one token per four letters, numbers as one token per three digits,
+/// CJK characters and punctuation as one token each, and other scripts as one token per two
+/// letters. Counts are estimates, so leave some headroom when a model's limit is strict.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApproxTokenizer;
+
+impl Tokenizer for ApproxTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        self.spans(text).len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Newline,
+    Space,
+    Digit,
+    Latin,
+    Cjk,
+    OtherLetter,
+    Symbol,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        match c {
+            '\n' | '\r' => CharClass::Newline,
+            _ if c.is_whitespace() => CharClass::Space,
+            _ if c.is_ascii_digit() => CharClass::Digit,
+            _ if is_cjk(c) => CharClass::Cjk,
+            // Basic Latin, Latin-1 Supplement and the Latin Extended blocks.
+            _ if c.is_alphabetic() && (c as u32) < 0x250 => CharClass::Latin,
+            _ if c.is_alphanumeric() => CharClass::OtherLetter,
+            _ => CharClass::Symbol,
+        }
+    }
+
+    // How many characters of this class are (roughly) merged into a single token.
+    fn chars_per_token(&self) -> usize {
+        match self {
+            CharClass::Latin => 4,
+            CharClass::Digit => 3,
+            CharClass::OtherLetter => 2,
+            CharClass::Newline => usize::MAX,
+            CharClass::Space | CharClass::Cjk | CharClass::Symbol => 1,
+        }
+    }
+}
+
+impl ApproxTokenizer {
+    /// Returns the byte ranges of the (approximate) tokens in the text.
+    pub(crate) fn spans(&self, text: &str) -> Vec<Range<usize>> {
+        let chars = text.char_indices().collect::<Vec<_>>();
+        let offset = |i: usize| {
+            chars
+                .get(i)
+                .map(|(offset, _)| *offset)
+                .unwrap_or(text.len())
+        };
+
+        let mut spans = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let class = CharClass::of(chars[i].1);
+            let mut end = i + 1;
+            while end < chars.len() && CharClass::of(chars[end].1) == class {
+                end += 1;
+            }
+
+            match class {
+                // A single space is merged into the following word, longer runs of spaces are a
+                // token of their own.
+                CharClass::Space => {
+                    if end - i > 1 {
+                        spans.push(offset(i)..offset(end));
+                    }
+                }
+                _ => {
+                    let step = class.chars_per_token();
+                    let mut start = i;
+                    while start < end {
+                        let stop = start.saturating_add(step).min(end);
+                        spans.push(offset(start)..offset(stop));
+                        start = stop;
+                    }
+                }
+            }
+
+            i = end;
+        }
+
+        spans
+    }
+}
+
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3040..=0x30FF // Hiragana and Katakana
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF // CJK Unified Ideographs
+            | 0xAC00..=0xD7AF // Hangul Syllables
+            | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_tokenizer() {
+        let tokenizer = ApproxTokenizer;
+
+        assert_eq!(tokenizer.count_tokens(""), 0);
+        assert_eq!(tokenizer.count_tokens("Hello world"), 4);
+        assert_eq!(tokenizer.count_tokens("Hello, world!"), 6);
+        assert_eq!(tokenizer.count_tokens("1234567"), 3);
+        assert_eq!(tokenizer.count_tokens("你好世界"), 4);
+        assert_eq!(tokenizer.count_tokens("one\n\ntwo"), 3);
+    }
+
+    #[test]
+    fn test_approx_tokenizer_spans() {
+        let text = "Héllo, 世界";
+        let spans = ApproxTokenizer.spans(text);
+        let tokens = spans.iter().map(|s| &text[s.clone()]).collect::<Vec<_>>();
+
+        assert_eq!(tokens, vec!["Héll", "o", ",", "世", "界"]);
+    }
+
+    #[test]
+    fn test_closure_tokenizer() {
+        let tokenizer = |text: &str| text.split_whitespace().count();
+        assert_eq!(tokenizer.count_tokens("one two three"), 3);
+    }
+}
@@ -1,28 +1,173 @@
 use crate::{
-    allocator,
-    error::{self, Error},
-    host, read_chunk_result, types,
+    allocator, chunker,
+    error::{self, DocumentError, Error},
+    host, language, network, read_chunk_result, safe_alloc,
+    tokenizer::Tokenizer,
+    types,
 };
 
+/// The strategy used by [`chunk`] to split text into chunks.
+///
+/// Host strategies call out to the host, local strategies run entirely inside the plugin and
+/// can be used offline (e.g. in tests).
+#[derive(Debug, Clone, Default)]
+pub enum ChunkStrategy {
+    /// Host-backed chunking with overlap, using the host's defaults (see [`chunk_with_overlap`])
+    #[default]
+    Overlap,
+    /// Host-backed chunking with overlap, using the given options (see [`chunk_with_options`])
+    OverlapWithOptions(types::ChunkOptions),
+    /// Host-backed chunking by sentence (see [`chunk_by_sentence`])
+    Sentence,
+    /// Local chunking by sentence
+    LocalSentence,
+    /// Local chunking by paragraph (blocks of text separated by blank lines)
+    LocalParagraph,
+    /// Local chunking that recursively splits on the configured separators until every chunk
+    /// fits, then merges small pieces back together with overlap
+    LocalRecursive(types::ChunkOptions),
+    /// Local chunking into fixed-size windows with overlap, ignoring separators
+    LocalFixedWindow(types::ChunkOptions),
+    /// Local chunking of Markdown along its headings, see [`chunk_markdown`]
+    LocalMarkdown(types::ChunkOptions),
+}
+
+/// Chunks the input string with the given strategy.
+///
+/// For host strategies the offsets are found by searching the input, and are empty ranges for
+/// chunks the host altered.
+///
+/// Each chunk is tagged with the language most of it is written in, using the language spans of
+/// the whole input (see [`crate::language::segment_by_language`]) so that short chunks of mixed
+/// language documents are labelled from their context. The other chunking functions of this module
+/// tag their chunks the same way.
+pub fn chunk(s: &str, strategy: &ChunkStrategy) -> Result<Vec<types::Chunk>, Error> {
+    let mut chunks = match strategy {
+        ChunkStrategy::Overlap => host_chunk_with_overlap(s)?,
+        ChunkStrategy::OverlapWithOptions(opts) => host_chunk_with_options(s, opts)?,
+        ChunkStrategy::Sentence => host_chunk_by_sentence(s)?,
+        ChunkStrategy::LocalSentence => chunker::by_sentence(s),
+        ChunkStrategy::LocalParagraph => chunker::by_paragraph(s),
+        ChunkStrategy::LocalRecursive(opts) => {
+            opts.validate()?;
+            chunker::recursive(s, opts)
+        }
+        ChunkStrategy::LocalFixedWindow(opts) => {
+            opts.validate()?;
+            chunker::fixed_window(s, opts)
+        }
+        ChunkStrategy::LocalMarkdown(opts) => {
+            opts.validate()?;
+            chunker::by_markdown_section(s, opts)
+        }
+    };
+
+    language::tag_chunks(s, &mut chunks);
+    Ok(chunks)
+}
+
+/// Chunks Markdown along its structure instead of its plain text.
+///
+/// Chunks are split on heading boundaries and never contain more than one section; code blocks,
+/// tables and HTML blocks are never split (even if they are larger than `max_size`), and other
+/// blocks are only split when they don't fit in a chunk on their own. Each chunk's text is prefixed
+/// with the headings it appears under (e.g. `Install > Linux`) to retain context. Front matter is
+/// skipped, and the chunk offsets still point into `markdown`.
+pub fn chunk_markdown(
+    markdown: &str,
+    opts: &types::ChunkOptions,
+) -> Result<Vec<types::Chunk>, Error> {
+    chunk(markdown, &ChunkStrategy::LocalMarkdown(opts.clone()))
+}
+
+/// Chunks the input string so that no chunk is larger than `max_tokens` as counted by the given
+/// tokenizer, repeating up to `overlap` tokens of the previous chunk at the start of the next one.
+///
+/// Text is split on paragraphs, then lines, sentences and words, and only in the middle of a word
+/// when a single word doesn't fit. Use [`crate::tokenizer::ApproxTokenizer`] when the model's
+/// tokenizer is not available.
+pub fn chunk_by_tokens(
+    s: &str,
+    tokenizer: &impl Tokenizer,
+    max_tokens: u32,
+    overlap: u32,
+) -> Result<Vec<types::Chunk>, Error> {
+    let opts = types::ChunkOptions {
+        max_size: max_tokens,
+        overlap,
+        unit: types::ChunkUnit::Tokens,
+        ..Default::default()
+    };
+    opts.validate()?;
+
+    let mut chunks = chunker::by_tokens(
+        s,
+        tokenizer,
+        max_tokens as usize,
+        overlap as usize,
+        &opts.separators,
+    );
+    language::tag_chunks(s, &mut chunks);
+    Ok(chunks)
+}
+
 /// Chunks the input string into smaller pieces with overlap (to retain context).
 /// This is useful for tasks like summarization or question answering.
-pub fn chunk_with_overlap(s: &str) -> Result<Vec<String>, Error> {
+pub fn chunk_with_overlap(s: &str) -> Result<Vec<types::Chunk>, Error> {
+    chunk(s, &ChunkStrategy::Overlap)
+}
+
+/// Chunks the input string into smaller pieces with overlap, using the given options to control
+/// the size of chunks, the overlap between them and where they are split.
+///
+/// [`chunk_with_overlap`] uses the host's defaults, which may differ from
+/// [`types::ChunkOptions::default`].
+pub fn chunk_with_options(s: &str, opts: &types::ChunkOptions) -> Result<Vec<types::Chunk>, Error> {
+    chunk(s, &ChunkStrategy::OverlapWithOptions(opts.clone()))
+}
+
+/// Chunks the input string into smaller pieces by sentence.
+pub fn chunk_by_sentence(s: &str) -> Result<Vec<types::Chunk>, Error> {
+    chunk(s, &ChunkStrategy::Sentence)
+}
+
+fn host_chunk_with_overlap(s: &str) -> Result<Vec<types::Chunk>, Error> {
     let (ptr, size) = unsafe { allocator::string_to_ptr(s) };
     let chunks = unsafe { host::chunk_with_overlap(ptr, size) };
 
     let (out_ptr, out_size) = allocator::decode_encoded_ptr("chunk_with_overlap", chunks)?;
 
-    read_chunk_result!(out_ptr, out_size)
+    let pieces: Result<Vec<String>, Error> = read_chunk_result!(out_ptr, out_size);
+    Ok(chunker::locate(s, pieces?))
 }
 
-/// Chunks the input string into smaller pieces by sentence.
-pub fn chunk_by_sentence(s: &str) -> Result<Vec<String>, Error> {
+fn host_chunk_with_options(
+    s: &str,
+    opts: &types::ChunkOptions,
+) -> Result<Vec<types::Chunk>, Error> {
+    opts.validate()?;
+
+    let message = types::ChunkRequest::new(s, opts).to_capnp_message()?;
+    let size = message.len() as u32;
+    let ptr = safe_alloc!("chunk_with_options", size);
+    allocator::write_to_memory(ptr, &message);
+
+    let chunks = unsafe { host::chunk_with_options(ptr, size) };
+
+    let (out_ptr, out_size) = allocator::decode_encoded_ptr("chunk_with_options", chunks)?;
+
+    let pieces: Result<Vec<String>, Error> = read_chunk_result!(out_ptr, out_size);
+    Ok(chunker::locate(s, pieces?))
+}
+
+fn host_chunk_by_sentence(s: &str) -> Result<Vec<types::Chunk>, Error> {
     let (ptr, size) = unsafe { allocator::string_to_ptr(s) };
     let chunks = unsafe { host::chunk_by_sentence(ptr, size) };
 
     let (out_ptr, out_size) = allocator::decode_encoded_ptr("chunk_by_sentence", chunks)?;
 
-    read_chunk_result!(out_ptr, out_size)
+    let pieces: Result<Vec<String>, Error> = read_chunk_result!(out_ptr, out_size);
+    Ok(chunker::locate(s, pieces?))
 }
 
 /// Fetches the content of a URL and converts it to Markdown format.
@@ -40,33 +185,293 @@ pub fn url_to_markdown(url: &str) -> Result<String, Error> {
     Ok(output)
 }
 
+/// Extracts the main content of a web page as Markdown, along with its title, author, publish
+/// date, canonical URL, language and lead image.
+///
+/// The input can either be a URL, which the host fetches and extracts, or the HTML of a page, which
+/// is extracted inside the plugin. This is useful to give link entries a proper name and metadata:
+///
+/// ```ignore
+/// let article = transform::extract_article(&url)?;
+/// entry::update(types::UpdateEntryOpts {
+///     id,
+///     name: article.title.clone(),
+///     content: Some(transform::md_to_content(&article.markdown)?),
+///     checksum: None,
+/// })?;
+/// ```
+pub fn extract_article(url_or_html: &str) -> Result<types::Article, Error> {
+    if url_or_html.trim().is_empty() {
+        return Err(Error::InvalidArguments(
+            "A URL or HTML document must be provided".to_string(),
+        ));
+    }
+
+    let source = types::ArticleSource::detect(url_or_html);
+    if let types::ArticleSource::Html(html) = source {
+        return Ok(crate::html::extract_article(html));
+    }
+
+    let message = source.to_capnp_message()?;
+    let size = message.len() as u32;
+    let ptr = safe_alloc!("extract_article", size);
+
+    allocator::write_to_memory(ptr, &message);
+
+    let result = unsafe { host::transform_extract_article(ptr, size) };
+    let (out_ptr, out_size) = allocator::decode_encoded_ptr("extract_article", result)?;
+
+    types::Article::read_from_memory(out_ptr, out_size)
+}
+
 /// Converts HTML content to Markdown format.
 /// This is useful for processing raw web pages or other HTML documents.
+///
+/// If the host fails to convert the HTML or returns nothing, it is converted with
+/// [`html_to_markdown_local`] instead, which returns [`Error::NoContent`] with the reason when the
+/// HTML has nothing to convert.
 pub fn html_to_markdown(html: &str) -> Result<String, Error> {
     let (ptr, size) = unsafe { allocator::string_to_ptr(html) };
     let result = unsafe { host::transform_html_to_markdown(ptr, size) };
 
-    let (out_ptr, out_size) = allocator::decode_encoded_ptr("html_to_markdown", result)?;
+    if let Ok((out_ptr, out_size)) = allocator::decode_encoded_ptr("html_to_markdown", result) {
+        let output = unsafe { allocator::ptr_to_string(out_ptr, out_size) };
+        if !output.trim().is_empty() {
+            return Ok(output);
+        }
+    }
 
-    let output = unsafe { allocator::ptr_to_string(out_ptr, out_size) };
-    if output.is_empty() {
-        return Err(Error::EmptyString);
+    html_to_markdown_local(html, &types::HtmlToMarkdownOptions::default())
+}
+
+/// Converts HTML content to Markdown inside the plugin, without a round-trip to the host.
+///
+/// Headings, paragraphs, emphasis, links, images, lists, tables, blockquotes and code blocks are
+/// converted, and by default only the main content of the page is kept (see
+/// [`types::HtmlToMarkdownOptions`]). Returns [`Error::NoContent`] if there is no text to convert.
+pub fn html_to_markdown_local(
+    html: &str,
+    opts: &types::HtmlToMarkdownOptions,
+) -> Result<String, Error> {
+    let markdown = crate::html::to_markdown(html, opts);
+    if markdown.is_empty() {
+        return Err(Error::NoContent {
+            context: "html_to_markdown".to_string(),
+            reason: if html.trim().is_empty() {
+                "the HTML is empty".to_string()
+            } else {
+                "the HTML has no text, images or links to convert".to_string()
+            },
+        });
     }
 
-    Ok(output)
+    Ok(markdown)
+}
+
+/// Converts the file behind an entry to Markdown.
+///
+/// Links are fetched and converted by the host (see [`url_to_markdown`]), other entries are
+/// downloaded from their URL and converted with [`document_to_markdown`].
+pub fn entry_to_markdown(entry: &types::Entry) -> Result<String, Error> {
+    if matches!(entry.r#type, types::Type::Link) {
+        return url_to_markdown(&entry.url);
+    }
+
+    let response = network::request(types::RequestOpts {
+        url: entry.url.clone(),
+        ..Default::default()
+    })?;
+    if !(200..300).contains(&response.status_code) {
+        return Err(Error::DocumentError(DocumentError::FetchFailed {
+            status: response.status_code,
+        }));
+    }
+
+    // Pre-signed URLs end with the object name, which keeps the extension of the uploaded file.
+    let path = entry.url.split(['?', '#']).next().unwrap_or_default();
+    let file_name = path
+        .rsplit('/')
+        .next()
+        .filter(|name| name.contains('.'))
+        .unwrap_or(entry.name.as_str());
+
+    document_to_markdown(&response.body, &entry.r#type, file_name)
+}
+
+/// Converts a document of the given type to Markdown.
+///
+/// DOCX, PPTX, XLSX, EPUB, CSV, source code and ZIP archives (as a listing of their files) are
+/// converted inside the plugin, other formats such as PDF are sent to the host. The file name is
+/// used to tell formats sharing a type apart, e.g. CSV and XLSX spreadsheets, and to tag code
+/// blocks with their language.
+pub fn document_to_markdown(
+    bytes: &[u8],
+    kind: &types::Type,
+    file_name: &str,
+) -> Result<String, Error> {
+    match kind {
+        types::Type::Link => {
+            return Err(Error::InvalidArguments(
+                "Links have no document to convert, use url_to_markdown instead".to_string(),
+            ));
+        }
+        types::Type::HTML => return html_to_markdown(&String::from_utf8_lossy(bytes)),
+        _ => (),
+    }
+
+    let markdown = match crate::document::to_markdown(bytes, kind, file_name) {
+        Some(markdown) => markdown?,
+        None => document_to_markdown_host(bytes, kind, file_name)?,
+    };
+
+    if markdown.trim().is_empty() {
+        return Err(Error::NoContent {
+            context: "document_to_markdown".to_string(),
+            reason: format!("{} ({}) has no text to convert", file_name, kind),
+        });
+    }
+
+    Ok(markdown)
+}
+
+fn document_to_markdown_host(
+    bytes: &[u8],
+    kind: &types::Type,
+    file_name: &str,
+) -> Result<String, Error> {
+    let message = types::DocumentRequest {
+        r#type: kind,
+        file_name,
+        content: bytes,
+    }
+    .to_capnp_message()?;
+    let size = message.len() as u32;
+    let ptr = safe_alloc!("document_to_markdown", size);
+
+    allocator::write_to_memory(ptr, &message);
+
+    let result = unsafe { host::transform_document_to_markdown(ptr, size) };
+    let (out_ptr, out_size) = allocator::decode_encoded_ptr("document_to_markdown", result)?;
+
+    Ok(unsafe { allocator::ptr_to_string(out_ptr, out_size) })
 }
 
 /// Generates an content type from the given markdown string.
 /// It strips the markdown formatting and returns a new content type with the plain text included.
+///
+/// Front matter is left in the markdown but not included in the plain text, use
+/// [`split_front_matter`] to read it.
 pub fn md_to_content(markdown: &str) -> Result<types::Content, error::Error> {
     if markdown.is_empty() {
         return Err(Error::EmptyString);
     }
 
-    let plain_text = crate::markdown::to_plain_text(markdown);
+    let (_, body) = crate::front_matter::split(markdown);
+    let plain_text = crate::markdown::to_plain_text(body);
+
+    Ok(types::Content {
+        markdown: markdown.to_string(),
+        plain_text,
+    })
+}
+
+/// Same as [`md_to_content`], but with options to control how the plain text is rendered (e.g.
+/// whether link URLs are kept).
+pub fn md_to_content_with(
+    markdown: &str,
+    opts: &types::PlainTextOptions,
+) -> Result<types::Content, error::Error> {
+    if markdown.is_empty() {
+        return Err(Error::EmptyString);
+    }
+
+    let (_, body) = crate::front_matter::split(markdown);
+    let plain_text = crate::markdown::to_plain_text_with(body, opts);
 
     Ok(types::Content {
         markdown: markdown.to_string(),
         plain_text,
     })
 }
+
+/// Splits the YAML (`---`) or TOML (`+++`) front matter off the top of the markdown, returning
+/// its fields along with the rest of the document.
+///
+/// The markdown is returned as-is when it has no front matter. Its title is usually what the
+/// entry should be named:
+///
+/// ```ignore
+/// let (front_matter, _) = transform::split_front_matter(&markdown);
+/// entry::update(types::UpdateEntryOpts {
+///     id,
+///     name: front_matter.as_ref().and_then(|fm| fm.title()).map(str::to_string),
+///     content: Some(transform::md_to_content(&markdown)?),
+///     checksum: None,
+/// })?;
+/// ```
+pub fn split_front_matter(markdown: &str) -> (Option<types::FrontMatter>, &str) {
+    crate::front_matter::split(markdown)
+}
+
+/// Returns the heading tree of the Markdown (with levels, anchors and byte ranges), along with all
+/// the links, images and code blocks it contains.
+///
+/// Byte ranges point into the given string, so sections can be sliced out of it directly.
+pub fn markdown_outline(markdown: &str) -> types::Outline {
+    crate::outline::outline(markdown)
+}
+
+/// Cleans up noisy Markdown, e.g. the output of [`html_to_markdown`] and [`url_to_markdown`].
+///
+/// Boilerplate (navigation menus, cookie banners, share sections), repeated blocks, extra
+/// whitespace, tracking query parameters and empty links are removed according to the options.
+/// The document is written back out in a normalised form, so the result may differ from the input
+/// in formatting (e.g. list markers) even if no rule applied.
+pub fn clean_markdown(markdown: &str, opts: &types::CleanOptions) -> Result<String, Error> {
+    crate::cleanup::clean(markdown, opts)
+}
+
+/// Renders Markdown to HTML, e.g. for previews or email digests.
+///
+/// GitHub Flavored Markdown extensions (tables, task lists, strikethrough and footnotes) are
+/// enabled and front matter is skipped. Raw HTML is escaped by default, see
+/// [`types::HtmlOptions`] to allow or strip it instead.
+pub fn markdown_to_html(markdown: &str, opts: &types::HtmlOptions) -> String {
+    crate::render::to_html(markdown, opts)
+}
+
+/// Summarises the text with the `n_sentences` sentences that best represent it, in the order they
+/// appear, e.g. for previews.
+///
+/// Sentences are ranked with TextRank, by how many words they share with the rest of the text
+/// (ignoring stopwords of the detected language), so this runs offline and is deterministic.
+/// Texts with no more than `n_sentences` sentences are returned whole.
+pub fn summarize(text: &str, n_sentences: usize) -> String {
+    crate::summary::summarize(text, n_sentences).join(" ")
+}
+
+/// Returns up to `n` keywords (or key phrases of up to three words) of the text, best first, e.g.
+/// to suggest tags.
+///
+/// Keywords are extracted with RAKE, splitting the text into candidates at punctuation and at the
+/// stopwords of its detected language.
+pub fn keywords(text: &str, n: usize) -> Vec<types::Keyword> {
+    crate::keywords::keywords(text, n)
+}
+
+/// Returns the word, sentence and paragraph counts of the content, its estimated reading time and
+/// its readability.
+///
+/// Counts are taken from the plain text, or from the Markdown if there is none. Readability indices
+/// depend on the detected language: LIX is computed for texts in the Latin, Cyrillic or Greek
+/// alphabet, Flesch Reading Ease for languages with an adaptation of the formula (English, German,
+/// French, Spanish, Italian, Dutch, Portuguese and Russian) and the Flesch–Kincaid grade for
+/// English only.
+pub fn stats(content: &types::Content) -> types::TextStats {
+    if content.plain_text.is_empty() && !content.markdown.is_empty() {
+        let (_, body) = crate::front_matter::split(&content.markdown);
+        return crate::stats::stats(&crate::markdown::to_plain_text(body));
+    }
+
+    crate::stats::stats(&content.plain_text)
+}
@@ -0,0 +1,128 @@
+use crate::{capnp_get_text, entry_capnp, error};
+
+/// The main content of a web page along with its metadata, as returned by
+/// `transform::extract_article`.
+///
+/// Metadata fields are `None` when the page doesn't provide them.
+#[derive(Debug, Clone, Default)]
+pub struct Article {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// The publish date as found on the page, usually in ISO 8601 format.
+    pub published_at: Option<String>,
+    /// The URL the page declares as its canonical one, which may differ from the requested URL
+    /// (e.g. without tracking parameters).
+    pub canonical_url: Option<String>,
+    /// The language tag the page declares, e.g. `en` or `en-US`.
+    pub language: Option<String>,
+    pub lead_image_url: Option<String>,
+    /// The main content of the page converted to Markdown.
+    pub markdown: String,
+}
+
+/// Where `transform::extract_article` reads the article from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ArticleSource<'a> {
+    /// A URL the host fetches the page from
+    Url(&'a str),
+    /// The HTML of an already fetched page
+    Html(&'a str),
+}
+
+impl<'a> ArticleSource<'a> {
+    /// Treats the input as a URL if it is a single HTTP(S) URL, and as HTML otherwise.
+    pub fn detect(url_or_html: &'a str) -> Self {
+        let trimmed = url_or_html.trim();
+        let is_url = (trimmed.starts_with("http://") || trimmed.starts_with("https://"))
+            && !trimmed.contains(char::is_whitespace);
+
+        if is_url {
+            ArticleSource::Url(trimmed)
+        } else {
+            ArticleSource::Html(url_or_html)
+        }
+    }
+
+    pub fn to_capnp_message(&self) -> Result<Vec<u8>, error::Error> {
+        let mut message = capnp::message::Builder::new_default();
+        let mut root = message.init_root::<entry_capnp::article_request::Builder>();
+
+        match self {
+            ArticleSource::Url(url) => root.set_url(url),
+            ArticleSource::Html(html) => root.set_html(html),
+        }
+
+        let mut buffer = vec![];
+        let mut cursor = std::io::Cursor::new(&mut buffer);
+        capnp::serialize::write_message(&mut cursor, &message).map_err(error::Error::Capnp)?;
+
+        Ok(buffer)
+    }
+}
+
+impl From<entry_capnp::article::Reader<'_>> for Article {
+    fn from(value: entry_capnp::article::Reader<'_>) -> Self {
+        let optional = |text: String| (!text.trim().is_empty()).then_some(text);
+
+        Article {
+            title: optional(capnp_get_text!(value.get_title())),
+            author: optional(capnp_get_text!(value.get_author())),
+            published_at: optional(capnp_get_text!(value.get_published_at())),
+            canonical_url: optional(capnp_get_text!(value.get_canonical_url())),
+            language: optional(capnp_get_text!(value.get_language())),
+            lead_image_url: optional(capnp_get_text!(value.get_lead_image_url())),
+            markdown: capnp_get_text!(value.get_markdown()),
+        }
+    }
+}
+
+impl Article {
+    pub fn read_from_memory(ptr: u32, len: u32) -> Result<Self, error::Error> {
+        crate::capnp_message_to_type!(ptr, len, entry_capnp::article::Reader, Article)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_url() {
+        assert!(matches!(
+            ArticleSource::detect("  https://example.com/post?id=1\n"),
+            ArticleSource::Url("https://example.com/post?id=1")
+        ));
+        assert!(matches!(
+            ArticleSource::detect("http://example.com"),
+            ArticleSource::Url("http://example.com")
+        ));
+    }
+
+    #[test]
+    fn test_detect_html() {
+        let html = "<html><body><a href=\"https://example.com\">link</a></body></html>";
+        assert!(
+            matches!(ArticleSource::detect(html), ArticleSource::Html(source) if source == html)
+        );
+        assert!(matches!(
+            ArticleSource::detect("https://example.com <b>not a URL</b>"),
+            ArticleSource::Html(_)
+        ));
+    }
+
+    #[test]
+    fn test_detect_garbage() {
+        // Anything that isn't a single HTTP(S) URL is treated as (possibly broken) HTML.
+        for input in [
+            "example.com",
+            "ftp://example.com/file",
+            "just some text",
+            "https//x",
+        ] {
+            assert!(matches!(
+                ArticleSource::detect(input),
+                ArticleSource::Html(source) if source == input
+            ));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{capnp_get_text, entry_capnp, error};
+use crate::{capnp_get_text, entry_capnp, error, language::Language};
 
 pub struct EntryChunk {
     pub id: i32,
@@ -22,6 +22,99 @@ pub struct CreateChunksOpts {
     pub chunks: Vec<NewChunk>,
 }
 
+/// A piece of a larger text, along with where it was found in that text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    pub text: String,
+    /// Byte offset of the start of the chunk in the original text.
+    pub start: usize,
+    /// Byte offset of the end (exclusive) of the chunk in the original text.
+    pub end: usize,
+    /// The headings the chunk appears under, outermost first. Only populated when chunking
+    /// Markdown.
+    pub heading_path: Vec<String>,
+    /// The language of the chunk's text, detected from the whole text it was taken from (see
+    /// [`crate::transform::chunk`]).
+    pub language: Language,
+}
+
+impl Chunk {
+    /// Creates a new chunk for the given entry from this chunk's text and language.
+    pub fn to_new_chunk(&self, entry_id: &str, index: i32, minimum_version: i32) -> NewChunk {
+        NewChunk {
+            entry_id: entry_id.to_string(),
+            index,
+            minimum_version,
+            content: self.text.clone(),
+            language: self.language.to_string(),
+        }
+    }
+}
+
+/// The unit used to measure the size of chunks and their overlap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChunkUnit {
+    /// Unicode characters (default)
+    #[default]
+    Characters,
+    /// Whitespace-separated words
+    Words,
+    /// Tokens, as counted by the host's tokenizer (or by `tokenizer::ApproxTokenizer` for local
+    /// chunking)
+    Tokens,
+}
+
+/// Options for chunking text with overlap.
+#[derive(Debug, Clone)]
+pub struct ChunkOptions {
+    /// The maximum size of a chunk, in `unit`s.
+    pub max_size: u32,
+    /// How much of the previous chunk is repeated at the start of the next one, in `unit`s.
+    pub overlap: u32,
+    pub unit: ChunkUnit,
+    /// Separators to split on, in order of preference. Text is only split in the middle of a
+    /// word when none of these can produce a small enough chunk.
+    pub separators: Vec<String>,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        ChunkOptions {
+            max_size: 1000,
+            overlap: 200,
+            unit: ChunkUnit::Characters,
+            separators: ["\n\n", "\n", ". ", " "]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl ChunkOptions {
+    pub(crate) fn validate(&self) -> Result<(), error::Error> {
+        if self.max_size == 0 {
+            return Err(error::Error::InvalidArguments(
+                "Chunk max_size must be greater than zero".to_string(),
+            ));
+        }
+
+        if self.overlap >= self.max_size {
+            return Err(error::Error::InvalidArguments(format!(
+                "Chunk overlap ({}) must be smaller than max_size ({})",
+                self.overlap, self.max_size
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) struct ChunkRequest<'a> {
+    text: &'a str,
+    options: &'a ChunkOptions,
+}
+
 impl From<entry_capnp::entry_chunk::Reader<'_>> for EntryChunk {
     fn from(value: entry_capnp::entry_chunk::Reader<'_>) -> Self {
         let entry_id = capnp_get_text!(value.get_entry_id());
@@ -60,6 +153,45 @@ impl EntryChunk {
     }
 }
 
+impl From<ChunkUnit> for entry_capnp::ChunkUnit {
+    fn from(value: ChunkUnit) -> Self {
+        match value {
+            ChunkUnit::Characters => entry_capnp::ChunkUnit::Characters,
+            ChunkUnit::Words => entry_capnp::ChunkUnit::Words,
+            ChunkUnit::Tokens => entry_capnp::ChunkUnit::Tokens,
+        }
+    }
+}
+
+impl ChunkRequest<'_> {
+    pub fn new<'a>(text: &'a str, options: &'a ChunkOptions) -> ChunkRequest<'a> {
+        ChunkRequest { text, options }
+    }
+
+    pub fn to_capnp_message(&self) -> Result<Vec<u8>, error::Error> {
+        let mut message = capnp::message::Builder::new_default();
+        let mut root = message.init_root::<entry_capnp::chunk_request::Builder>();
+
+        root.set_text(self.text);
+        root.set_max_size(self.options.max_size);
+        root.set_overlap(self.options.overlap);
+        root.set_unit(self.options.unit.into());
+
+        let mut separators = root
+            .reborrow()
+            .init_separators(self.options.separators.len() as u32);
+        for (i, separator) in self.options.separators.iter().enumerate() {
+            separators.set(i as u32, separator.as_str());
+        }
+
+        let mut buffer = vec![];
+        let mut cursor = std::io::Cursor::new(&mut buffer);
+        capnp::serialize::write_message(&mut cursor, &message).map_err(error::Error::Capnp)?;
+
+        Ok(buffer)
+    }
+}
+
 impl CreateChunksOpts {
     pub fn to_capnp_message(&self) -> Result<Vec<u8>, error::Error> {
         let mut message = capnp::message::Builder::new_default();
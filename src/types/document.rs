@@ -0,0 +1,27 @@
+use crate::{entry_capnp, error};
+
+use super::Type;
+
+/// A document the host converts to Markdown, for formats the plugin can't read itself (e.g. PDF).
+pub(crate) struct DocumentRequest<'a> {
+    pub r#type: &'a Type,
+    pub file_name: &'a str,
+    pub content: &'a [u8],
+}
+
+impl DocumentRequest<'_> {
+    pub fn to_capnp_message(&self) -> Result<Vec<u8>, error::Error> {
+        let mut message = capnp::message::Builder::new_default();
+        let mut root = message.init_root::<entry_capnp::document_request::Builder>();
+
+        root.set_type(self.r#type.into());
+        root.set_file_name(self.file_name);
+        root.set_content(self.content);
+
+        let mut buffer = vec![];
+        let mut cursor = std::io::Cursor::new(&mut buffer);
+        capnp::serialize::write_message(&mut cursor, &message).map_err(error::Error::Capnp)?;
+
+        Ok(buffer)
+    }
+}
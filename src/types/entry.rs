@@ -137,6 +137,30 @@ impl From<entry_capnp::Type> for Type {
     }
 }
 
+impl From<&Type> for entry_capnp::Type {
+    fn from(value: &Type) -> Self {
+        match value {
+            Type::Link => entry_capnp::Type::Link,
+            Type::Audio => entry_capnp::Type::Audio,
+            Type::Video => entry_capnp::Type::Video,
+            Type::Image => entry_capnp::Type::Image,
+            Type::PDF => entry_capnp::Type::Pdf,
+            Type::Interchange => entry_capnp::Type::Interchange,
+            Type::EPUB => entry_capnp::Type::Epub,
+            Type::WordDocument => entry_capnp::Type::WordDocument,
+            Type::Presentation => entry_capnp::Type::Presentation,
+            Type::Spreadsheet => entry_capnp::Type::Spreadsheet,
+            Type::HTML => entry_capnp::Type::Html,
+            Type::Markdown => entry_capnp::Type::Markdown,
+            Type::PlainText => entry_capnp::Type::PlainText,
+            Type::Archive => entry_capnp::Type::Archive,
+            Type::Code => entry_capnp::Type::Code,
+            Type::Comment => entry_capnp::Type::Comment,
+            Type::Other => entry_capnp::Type::Other,
+        }
+    }
+}
+
 impl From<entry_capnp::owner::Reader<'_>> for Owner {
     fn from(value: entry_capnp::owner::Reader<'_>) -> Self {
         let first_name = capnp_get_text!(value.get_first_name());
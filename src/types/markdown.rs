@@ -0,0 +1,235 @@
+use std::{collections::BTreeMap, ops::Range};
+
+/// How links are rendered when converting Markdown to plain text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinkStyle {
+    /// Only the link text is kept (default)
+    #[default]
+    Drop,
+    /// The URL follows the link text in parentheses, e.g. `Hubble (https://example.com)`
+    Inline,
+    /// The link text is followed by a numbered reference, e.g. `Hubble [1]`, and the URLs are
+    /// listed at the end of the text
+    Footnote,
+}
+
+/// Options for converting Markdown to plain text.
+#[derive(Debug, Clone, Default)]
+pub struct PlainTextOptions {
+    pub links: LinkStyle,
+}
+
+/// The structure of a Markdown document and the resources it references.
+#[derive(Debug, Clone, Default)]
+pub struct Outline {
+    /// Top-level headings, with lower-level headings nested in their `children`.
+    pub headings: Vec<Heading>,
+    pub links: Vec<Link>,
+    pub images: Vec<Image>,
+    pub code_blocks: Vec<CodeBlock>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Heading {
+    /// The heading level, from 1 to 6.
+    pub level: u8,
+    /// The heading text, without any formatting.
+    pub text: String,
+    /// A URL fragment for the heading, either its explicit `{#id}` or a GitHub-style slug of its
+    /// text. Anchors are unique within a document.
+    pub anchor: String,
+    /// Byte range of the heading itself in the Markdown.
+    pub range: Range<usize>,
+    /// Byte range of the heading and its content, up to the next heading of the same or a
+    /// higher level.
+    pub section: Range<usize>,
+    pub children: Vec<Heading>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+    pub title: String,
+    /// Byte range of the link in the Markdown.
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub alt: String,
+    pub url: String,
+    pub title: String,
+    /// Byte range of the image in the Markdown.
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    /// The language of a fenced code block (the first word of its info string), if any.
+    pub language: Option<String>,
+    pub code: String,
+    /// Byte range of the code block (including fences) in the Markdown.
+    pub range: Range<usize>,
+}
+
+/// The syntax front matter was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontMatterFormat {
+    /// YAML between `---` lines
+    Yaml,
+    /// TOML between `+++` lines
+    Toml,
+}
+
+/// A value in front matter, TOML dates and times are kept as strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontMatterValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<FrontMatterValue>),
+    Map(BTreeMap<String, FrontMatterValue>),
+}
+
+impl FrontMatterValue {
+    /// Returns the value if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FrontMatterValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// The metadata block at the top of a Markdown document, e.g. its title, tags or date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    pub format: FrontMatterFormat,
+    pub fields: BTreeMap<String, FrontMatterValue>,
+}
+
+impl FrontMatter {
+    /// Returns the value of a top-level field.
+    pub fn get(&self, key: &str) -> Option<&FrontMatterValue> {
+        self.fields.get(key)
+    }
+
+    /// Returns the document's title, if the front matter has a non-empty `title` field.
+    ///
+    /// This is typically what an entry should be named after, e.g. as
+    /// [`crate::types::UpdateEntryOpts::name`].
+    pub fn title(&self) -> Option<&str> {
+        self.get("title")
+            .and_then(FrontMatterValue::as_str)
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+    }
+}
+
+/// Phrases that mark a heading's section or a short block as boilerplate, see
+/// [`CleanOptions::boilerplate`].
+pub const DEFAULT_BOILERPLATE: &[&str] = &[
+    "accept cookies",
+    "advertisement",
+    "all rights reserved",
+    "cookie policy",
+    "cookie settings",
+    "follow us",
+    "related articles",
+    "related posts",
+    "share this",
+    "sign up for our newsletter",
+    "skip to content",
+    "subscribe to our newsletter",
+    "we use cookies",
+];
+
+/// Query parameters removed from link and image URLs by [`CleanOptions::remove_tracking_params`],
+/// a trailing `*` matches any parameter with that prefix.
+pub const TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "fbclid", "gclid", "dclid", "msclkid", "yclid", "mc_cid", "mc_eid", "igshid",
+    "_hsenc", "_hsmi", "mkt_tok", "ref_src",
+];
+
+/// Rules applied by `transform::clean_markdown`, all of them are enabled by default.
+#[derive(Debug, Clone)]
+pub struct CleanOptions {
+    /// Removes sections whose heading contains one of the `boilerplate` phrases, short blocks
+    /// containing one of them (e.g. cookie banners), and lists or paragraphs made up only of
+    /// links before the first heading (e.g. navigation menus).
+    pub strip_boilerplate: bool,
+    /// Lowercase phrases used by `strip_boilerplate`, defaults to [`DEFAULT_BOILERPLATE`].
+    pub boilerplate: Vec<String>,
+    /// Collapses runs of whitespace in text (outside of code) and removes empty blocks.
+    pub collapse_whitespace: bool,
+    /// Removes blocks whose text is identical to an earlier block, headings and code blocks are
+    /// always kept.
+    pub dedupe_lines: bool,
+    /// Removes the [`TRACKING_PARAMS`] from link and image URLs.
+    pub remove_tracking_params: bool,
+    /// Removes links without any text, and turns links without a URL into plain text.
+    pub remove_empty_links: bool,
+}
+
+impl Default for CleanOptions {
+    fn default() -> Self {
+        CleanOptions {
+            strip_boilerplate: true,
+            boilerplate: DEFAULT_BOILERPLATE.iter().map(|s| s.to_string()).collect(),
+            collapse_whitespace: true,
+            dedupe_lines: true,
+            remove_tracking_params: true,
+            remove_empty_links: true,
+        }
+    }
+}
+
+/// How raw HTML embedded in Markdown is rendered by `transform::markdown_to_html`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RawHtml {
+    /// Raw HTML is passed through as-is, only use this for trusted Markdown
+    Allow,
+    /// Raw HTML is escaped and shown as text (default)
+    #[default]
+    Escape,
+    /// Raw HTML is removed
+    Strip,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlOptions {
+    /// Gives every heading an `id` (its explicit `{#id}` or a GitHub-style slug of its text), so
+    /// sections can be linked to. Enabled by default.
+    pub heading_anchors: bool,
+    /// How raw HTML is rendered. Unless it is [`RawHtml::Allow`], links and images with
+    /// `javascript:`, `vbscript:` or non-image `data:` URLs are also neutralised.
+    pub raw_html: RawHtml,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            heading_anchors: true,
+            raw_html: RawHtml::default(),
+        }
+    }
+}
+
+/// Options for converting HTML to Markdown inside the plugin.
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlToMarkdownOptions {
+    /// Only converts the main content of the page, leaving out navigation, sidebars, footers,
+    /// cookie banners and the like. Enabled by default.
+    pub extract_main_content: bool,
+}
+
+impl Default for HtmlToMarkdownOptions {
+    fn default() -> Self {
+        HtmlToMarkdownOptions {
+            extract_main_content: true,
+        }
+    }
+}
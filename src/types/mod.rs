@@ -1,9 +1,17 @@
+mod article;
 mod chunk;
+mod document;
 mod entry;
+mod markdown;
 mod network;
 mod store;
+mod text;
 
+pub use article::*;
 pub use chunk::*;
+pub(crate) use document::*;
 pub use entry::*;
+pub use markdown::*;
 pub use network::*;
 pub(crate) use store::*;
+pub use text::*;
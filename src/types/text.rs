@@ -0,0 +1,50 @@
+use crate::language::Language;
+
+/// A keyword or key phrase of a text, as returned by `transform::keywords`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyword {
+    /// The keyword in lowercase, e.g. `memory safety`.
+    pub text: String,
+    /// How relevant the keyword is to the text. Scores are only comparable within the same text.
+    pub score: f64,
+}
+
+/// Counts, reading time and readability of a text, as returned by `transform::stats`.
+#[derive(Debug, Clone)]
+pub struct TextStats {
+    /// Words, where each CJK character counts as a word.
+    pub words: usize,
+    pub sentences: usize,
+    pub paragraphs: usize,
+    /// Characters, whitespace excluded.
+    pub characters: usize,
+    /// Estimated time to read the text, at an average silent reading speed.
+    pub reading_time_seconds: u64,
+    /// The detected language of the text, which the readability formulas depend on.
+    pub language: Language,
+    /// `None` if the text has no words to score.
+    pub readability: Option<Readability>,
+}
+
+impl TextStats {
+    /// Returns the reading time rounded up to whole minutes, e.g. for a "5 min read" label.
+    pub fn reading_time_minutes(&self) -> u64 {
+        self.reading_time_seconds.div_ceil(60)
+    }
+}
+
+/// Readability indices of a text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Readability {
+    /// The Flesch Reading Ease score, from 0 (very hard) to 100 (very easy), using the adaptation
+    /// of the formula for the language (e.g. Amstad's for German, Kandel and Moles' for French).
+    /// `None` for languages without an adaptation.
+    pub flesch_reading_ease: Option<f64>,
+    /// The Flesch–Kincaid grade level, i.e. the US school grade needed to understand the text.
+    /// Only computed for English.
+    pub flesch_kincaid_grade: Option<f64>,
+    /// The LIX score, which only depends on sentence and word lengths and so works across
+    /// languages: below 30 is very easy, above 60 very hard. `None` for texts that are not written
+    /// in the Latin, Cyrillic or Greek alphabet, where word lengths are not comparable.
+    pub lix: Option<f64>,
+}